@@ -0,0 +1,94 @@
+//! Compares picking random words by rescanning the word list for every game (how
+//! `random_word_from_file` used to work) against picking from a `WordIndex` built once.
+//!
+//! Run with `cargo bench --bench word_selection`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rusty_hangman::{Difficulty, WordFilter, WordIndex};
+
+/// The number of words picked when timing each approach.
+const SCAN_PICKS: u32 = 100;
+const INDEX_PICKS: u32 = 100_000;
+
+fn main() {
+    let mut rng = StdRng::seed_from_u64(0x6a6e_6761_6d65);
+    let filter = Difficulty::Normal.profile().words;
+
+    println!(
+        "{:>10} | {:>16} | {:>16} | {:>16} | {:>8}",
+        "words", "rescan per pick", "index build", "index per pick", "speedup"
+    );
+
+    let embedded = include_str!("../worldlist/wordlist.txt");
+    bench("embedded", embedded.to_string(), &filter, &mut rng);
+
+    for size in [100_000, 250_000] {
+        let contents = synthetic_word_list(size, &mut rng);
+        bench(&size.to_string(), contents, &filter, &mut rng);
+    }
+}
+
+/// Time both approaches on one word list and print a row of the results table.
+fn bench(label: &str, contents: String, filter: &WordFilter, rng: &mut StdRng) {
+    let start = Instant::now();
+    for _ in 0..SCAN_PICKS {
+        black_box(rescan_random_word(&contents, filter, rng));
+    }
+    let scan_per_pick = start.elapsed() / SCAN_PICKS;
+
+    let start = Instant::now();
+    let index = WordIndex::new(contents.lines().map(str::to_string).collect());
+    let build = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..INDEX_PICKS {
+        black_box(index.random_word(filter, rng));
+    }
+    let index_per_pick = start.elapsed() / INDEX_PICKS;
+
+    println!(
+        "{:>10} | {:>16} | {:>16} | {:>16} | {:>7.0}x",
+        label,
+        format_duration(scan_per_pick),
+        format_duration(build),
+        format_duration(index_per_pick),
+        scan_per_pick.as_secs_f64() / index_per_pick.as_secs_f64().max(f64::EPSILON)
+    );
+}
+
+/// Pick a word the way `random_word_from_file` did before the index: split and filter every
+/// line into a fresh `Vec<String>`, then pick from it.
+fn rescan_random_word(contents: &str, filter: &WordFilter, rng: &mut StdRng) -> Option<String> {
+    let mut words = Vec::new();
+    for line in contents.lines() {
+        if filter.matches(line) {
+            words.push(line.to_string());
+        }
+    }
+
+    if words.is_empty() {
+        return None;
+    }
+    Some(words[rng.gen_range(0..words.len())].clone())
+}
+
+/// Build a word list of random lowercase words, 2 to 14 letters long, one per line.
+fn synthetic_word_list(size: usize, rng: &mut StdRng) -> String {
+    let mut contents = String::new();
+    for _ in 0..size {
+        let length = rng.gen_range(2..=14);
+        for _ in 0..length {
+            contents.push(rng.gen_range(b'a'..=b'z') as char);
+        }
+        contents.push('\n');
+    }
+    contents
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The letters a player can guess in English, in the order front-ends display them.
+pub const ALPHABET: [char; 26] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+const GERMAN: [char; 30] = [
+    'A', 'Ä', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'Ö', 'P', 'Q',
+    'R', 'S', 'ẞ', 'T', 'U', 'Ü', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+const SPANISH: [char; 27] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'Ñ', 'O', 'P', 'Q', 'R',
+    'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+// Q, V and X are not native Polish letters but appear in loanwords, so they stay on the keyboard
+const POLISH: [char; 35] = [
+    'A', 'Ą', 'B', 'C', 'Ć', 'D', 'E', 'Ę', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'Ł', 'M', 'N', 'Ń',
+    'O', 'Ó', 'P', 'Q', 'R', 'S', 'Ś', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', 'Ź', 'Ż',
+];
+
+const RUSSIAN: [char; 33] = [
+    'А', 'Б', 'В', 'Г', 'Д', 'Е', 'Ё', 'Ж', 'З', 'И', 'Й', 'К', 'Л', 'М', 'Н', 'О', 'П', 'Р', 'С',
+    'Т', 'У', 'Ф', 'Х', 'Ц', 'Ч', 'Ш', 'Щ', 'Ъ', 'Ы', 'Ь', 'Э', 'Ю', 'Я',
+];
+
+const GREEK: [char; 24] = [
+    'Α', 'Β', 'Γ', 'Δ', 'Ε', 'Ζ', 'Η', 'Θ', 'Ι', 'Κ', 'Λ', 'Μ', 'Ν', 'Ξ', 'Ο', 'Π', 'Ρ', 'Σ', 'Τ',
+    'Υ', 'Φ', 'Χ', 'Ψ', 'Ω',
+];
+
+/// Punctuation marks, as opposed to other symbols. Apostrophes, hyphens and dashes count as punctuation.
+const PUNCTUATION: &[char] = &[
+    '\'', '"', '-', '.', ',', '!', '?', ':', ';', '(', ')', '[', ']', '{', '}', '/', '\\', '¡',
+    '¿', '‘', '’', '“', '”', '«', '»', '‹', '›', '–', '—', '…', '·',
+];
+
+/// The kinds of characters a phrase is made of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CharClass {
+    Letter,
+    Whitespace,
+    Digit,
+    Punctuation,
+    Symbol,
+}
+
+/// The kinds of characters in a phrase that are shown from the start instead of being guessed.
+///
+/// Letters always have to be guessed and whitespace is always shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AutoReveal {
+    /// Flag indicating if punctuation such as `'`, `-` and `!` is shown from the start.
+    pub punctuation: bool,
+    /// Flag indicating if digits are shown from the start.
+    pub digits: bool,
+    /// Flag indicating if other symbols such as `&`, `#` and `€` are shown from the start.
+    pub symbols: bool,
+}
+
+impl Default for AutoReveal {
+    /// Show everything but letters from the start, as none of it can be guessed from the keyboard.
+    fn default() -> Self {
+        Self {
+            punctuation: true,
+            digits: true,
+            symbols: true,
+        }
+    }
+}
+
+impl AutoReveal {
+    /// Check whether a character of the phrase is shown without being guessed.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The character to check.
+    ///
+    pub fn reveals(&self, c: char) -> bool {
+        match char_class(c) {
+            CharClass::Letter => false,
+            CharClass::Whitespace => true,
+            CharClass::Digit => self.digits,
+            CharClass::Punctuation => self.punctuation,
+            CharClass::Symbol => self.symbols,
+        }
+    }
+}
+
+/// Classify a character of a phrase.
+///
+/// # Arguments
+///
+/// * `c` - The character to classify.
+///
+pub fn char_class(c: char) -> CharClass {
+    if c.is_alphabetic() {
+        CharClass::Letter
+    } else if c.is_whitespace() {
+        CharClass::Whitespace
+    } else if c.is_numeric() {
+        CharClass::Digit
+    } else if PUNCTUATION.contains(&c) {
+        CharClass::Punctuation
+    } else {
+        CharClass::Symbol
+    }
+}
+
+/// The languages the Hangman game has alphabets for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+    Polish,
+    Russian,
+    Greek,
+}
+
+impl Language {
+    /// Every language, in the order they are offered to the player.
+    pub const ALL: [Language; 7] = [
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Spanish,
+        Language::Polish,
+        Language::Russian,
+        Language::Greek,
+    ];
+
+    /// The name of the language, as shown to the player.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+            Language::Spanish => "Español",
+            Language::Polish => "Polski",
+            Language::Russian => "Русский",
+            Language::Greek => "Ελληνικά",
+        }
+    }
+
+    /// The letters a player can guess in this language, in alphabetical order.
+    pub fn alphabet(&self) -> &'static [char] {
+        match self {
+            Language::English | Language::French => &ALPHABET,
+            Language::German => &GERMAN,
+            Language::Spanish => &SPANISH,
+            Language::Polish => &POLISH,
+            Language::Russian => &RUSSIAN,
+            Language::Greek => &GREEK,
+        }
+    }
+
+    /// Whether guesses in this language match accented letters by default.
+    ///
+    /// Accents are folded where they are not separate letters of the alphabet, so French and
+    /// Spanish phrases are playable on a plain keyboard. Languages whose diacritics make
+    /// distinct letters have every such letter on their keyboard, so they don't fold by default.
+    pub fn folds_accents(&self) -> bool {
+        match self {
+            Language::English | Language::French | Language::Spanish | Language::Greek => true,
+            Language::German | Language::Polish | Language::Russian => false,
+        }
+    }
+
+    /// Lay the alphabet out as rows of an on-screen keyboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `row_length` - The most keys in a row.
+    ///
+    pub fn keyboard_rows(&self, row_length: usize) -> Vec<&'static [char]> {
+        self.alphabet().chunks(row_length.max(1)).collect()
+    }
+
+    /// Check whether a letter can be guessed in this language.
+    ///
+    /// # Arguments
+    ///
+    /// * `letter` - The letter to check, in any case.
+    ///
+    pub fn contains(&self, letter: char) -> bool {
+        self.alphabet().contains(&normalize_letter(letter))
+    }
+}
+
+/// Uppercase a guessed letter the way phrases are uppercased by `normalize_phrase`.
+///
+/// Letters whose uppercase form is more than one character (like `ß`, which `char::to_uppercase`
+/// turns into `SS`) are mapped to a single capital where one exists and kept as they are
+/// otherwise, so every letter stays a single guessable character.
+///
+/// # Arguments
+///
+/// * `letter` - The letter to uppercase.
+///
+pub fn normalize_letter(letter: char) -> char {
+    if letter == 'ß' {
+        return 'ẞ';
+    }
+
+    let mut upper = letter.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) => upper,
+        _ => letter,
+    }
+}
+
+/// Put a phrase in the form the game plays with: NFC-normalized and uppercased letter by letter.
+///
+/// # Arguments
+///
+/// * `phrase` - The phrase to normalize.
+///
+pub fn normalize_phrase(phrase: &str) -> String {
+    phrase.nfc().map(normalize_letter).collect()
+}
+
+/// The letter that reveals a letter of the phrase.
+///
+/// With accent folding, a letter that is not in the alphabet (like `É` in French) is revealed by
+/// its base letter (`E`). Letters that are in the alphabet (like `Ñ` in Spanish) always stay
+/// distinct.
+///
+/// # Arguments
+///
+/// * `letter` - The letter of the phrase, as returned by `normalize_letter`.
+/// * `language` - The language the game is played in.
+/// * `fold_accents` - Whether accented letters are revealed by their base letter.
+///
+pub fn guess_key(letter: char, language: Language, fold_accents: bool) -> char {
+    if !fold_accents || language.alphabet().contains(&letter) {
+        return letter;
+    }
+
+    match letter.nfd().next() {
+        Some(base) if language.alphabet().contains(&base) => base,
+        _ => letter,
+    }
+}
+
+/// Split a normalized phrase into grapheme clusters, each with the letter that reveals it.
+///
+/// A cluster is revealed by its first character, so a letter followed by combining marks that
+/// have no precomposed form is still guessed by its base letter.
+///
+/// # Arguments
+///
+/// * `phrase` - The phrase to split, as returned by `normalize_phrase`.
+///
+pub fn graphemes_with_letters(phrase: &str) -> Vec<(&str, char)> {
+    phrase
+        .graphemes(true)
+        .filter_map(|grapheme| Some((grapheme, grapheme.chars().next()?)))
+        .collect()
+}
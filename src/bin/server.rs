@@ -0,0 +1,439 @@
+//! Multiplayer server for Rusty Hangman, hosting rooms whose players take turns guessing the
+//! same phrase over the network.
+//!
+//! Each room owns its `HangmanGameState`, so clients can't change a game except through the
+//! messages of the protocol described in `rusty_hangman::net`, and only ever see it through a
+//! `GameView`. Spectators get views without the phrase. A room's game starts with its first
+//! guess or solve attempt, so everyone who joins before then plays in it.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+use rusty_hangman::cli::{CliOptions, USAGE};
+use rusty_hangman::net::{write_message, DEFAULT_ADDRESS};
+use rusty_hangman::{
+    embedded_word_source, open_word_source, ClientMessage, Difficulty, GameView, HangmanGameState,
+    Language, ServerMessage, WordSource,
+};
+
+/// A client in a room.
+struct Member {
+    /// The number of the client's connection.
+    id: usize,
+    /// The name the client plays under, or `None` for a spectator.
+    name: Option<String>,
+    /// Where messages for the client are queued to be written to it.
+    outgoing: Sender<ServerMessage>,
+}
+
+/// A room: the game its players share, and the clients in it.
+struct Room {
+    /// The room's current game.
+    game_state: HangmanGameState,
+    /// The clients in the room, in the order they joined.
+    members: Vec<Member>,
+}
+
+/// The rooms of the server.
+struct Server {
+    /// The rooms that have clients in them, by name.
+    rooms: HashMap<String, Room>,
+    /// The source the phrases of every room are picked from.
+    word_source: Arc<dyn WordSource>,
+}
+
+fn main() -> io::Result<()> {
+    let cli_options = match CliOptions::from_env() {
+        Ok(cli_options) => cli_options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli_options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let word_source = match &cli_options.words {
+        Some(path) => match open_word_source(path) {
+            Ok(word_source) => word_source,
+            Err(error) => {
+                eprintln!("Could not load {}: {}", path.display(), error);
+                std::process::exit(1);
+            }
+        },
+        None => embedded_word_source(Language::default()),
+    };
+    let server = Arc::new(Mutex::new(Server {
+        rooms: HashMap::new(),
+        word_source,
+    }));
+
+    let address = cli_options
+        .server
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let listener = TcpListener::bind(&address)?;
+    println!(
+        "Rusty Hangman server listening on {}",
+        listener.local_addr()?
+    );
+
+    for (id, stream) in listener.incoming().enumerate() {
+        match stream {
+            Ok(stream) => {
+                let server = server.clone();
+                thread::spawn(move || match handle_client(stream, id, &server) {
+                    // A client that quits without closing the connection cleanly still just left
+                    Err(error) if error.kind() != io::ErrorKind::ConnectionReset => {
+                        eprintln!("Connection {} failed: {}", id, error);
+                    }
+                    _ => {}
+                });
+            }
+            Err(error) => eprintln!("Could not accept a connection: {}", error),
+        }
+    }
+    Ok(())
+}
+
+/// Serve one client until it disconnects: read its messages and act on them, while a thread
+/// of its own writes the messages queued for it.
+///
+/// # Arguments
+///
+/// * `stream` - The client's connection.
+/// * `id` - The number of the client's connection.
+/// * `server` - The rooms, shared with the other clients.
+///
+fn handle_client(stream: TcpStream, id: usize, server: &Mutex<Server>) -> io::Result<()> {
+    let (outgoing, queued) = mpsc::channel::<ServerMessage>();
+    let mut writer = stream.try_clone()?;
+    thread::spawn(move || {
+        for message in queued {
+            if write_message(&mut writer, &message).is_err() {
+                break;
+            }
+        }
+    });
+
+    // The room the client is in, once it has joined one
+    let mut joined: Option<String> = None;
+    let result = BufReader::new(stream).lines().try_for_each(|line| {
+        let line = line?;
+        if line.trim().is_empty() {
+            return Ok(());
+        }
+        let message = match serde_json::from_str::<ClientMessage>(&line) {
+            Ok(message) => message,
+            Err(error) => {
+                let message = format!("Invalid message: {}", error);
+                let _ = outgoing.send(ServerMessage::Error { message });
+                return Ok(());
+            }
+        };
+
+        let mut server = server.lock().unwrap_or_else(PoisonError::into_inner);
+        let error = match (&joined, message) {
+            (
+                None,
+                ClientMessage::Join {
+                    room,
+                    name,
+                    difficulty,
+                },
+            ) => match server.join(id, &room, &name, difficulty, &outgoing) {
+                Ok(()) => {
+                    joined = Some(room);
+                    None
+                }
+                Err(message) => Some(message),
+            },
+            (None, ClientMessage::Watch { room }) => match server.watch(id, &room, &outgoing) {
+                Ok(()) => {
+                    joined = Some(room);
+                    None
+                }
+                Err(message) => Some(message),
+            },
+            (None, _) => Some("Join a room first.".to_string()),
+            (Some(_), ClientMessage::Join { .. } | ClientMessage::Watch { .. }) => {
+                Some("You are already in a room.".to_string())
+            }
+            (Some(room), message) => server.play(room, id, message).err(),
+        };
+        if let Some(message) = error {
+            let _ = outgoing.send(ServerMessage::Error { message });
+        }
+        Ok(())
+    });
+
+    if let Some(room) = joined {
+        server
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .leave(&room, id);
+    }
+    result
+}
+
+impl Server {
+    /// Add a client to a room as a player, creating the room and its first game if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The number of the client's connection.
+    /// * `room` - The name of the room.
+    /// * `name` - The player's name, which must not be taken in the room.
+    /// * `difficulty` - The difficulty of a new room, `Normal` if not given.
+    /// * `outgoing` - Where to queue the messages for the client.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An error for the client if it can't join.
+    fn join(
+        &mut self,
+        id: usize,
+        room: &str,
+        name: &str,
+        difficulty: Option<Difficulty>,
+        outgoing: &Sender<ServerMessage>,
+    ) -> Result<(), String> {
+        let name = name.trim();
+        if room.trim().is_empty() || name.is_empty() {
+            return Err("Joining needs a room and a name.".to_string());
+        }
+
+        let word_source = self.word_source.clone();
+        let room_state = self.rooms.entry(room.to_string()).or_insert_with(|| {
+            // The game is set up until its first guess, so the players can gather first
+            let game_state = HangmanGameState::builder()
+                .difficulty(difficulty.unwrap_or_default())
+                .word_source(word_source)
+                .build();
+            Room {
+                game_state,
+                members: Vec::new(),
+            }
+        });
+        if (room_state.members.iter()).any(|member| member.name.as_deref() == Some(name)) {
+            return Err(format!(
+                "There is already a player called {} in {}.",
+                name, room
+            ));
+        }
+
+        room_state.members.push(Member {
+            id,
+            name: Some(name.to_string()),
+            outgoing: outgoing.clone(),
+        });
+        let _ = outgoing.send(ServerMessage::Joined {
+            room: room.to_string(),
+            name: name.to_string(),
+        });
+        // Players who join a game in progress take their turns from the next game
+        if room_state.game_state.add_player(name.to_string()) {
+            room_state.send_state(format!(
+                "{} joined. The game starts with the first guess.",
+                name
+            ));
+        } else {
+            room_state.send_state(format!("{} joined and plays from the next game.", name));
+        }
+        Ok(())
+    }
+
+    /// Add a client to a room as a spectator.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The number of the client's connection.
+    /// * `room` - The name of the room, which must have clients in it.
+    /// * `outgoing` - Where to queue the messages for the client.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An error for the client if there is no such room.
+    fn watch(
+        &mut self,
+        id: usize,
+        room: &str,
+        outgoing: &Sender<ServerMessage>,
+    ) -> Result<(), String> {
+        let room_state = (self.rooms.get_mut(room))
+            .ok_or_else(|| format!("There is no room called {}.", room))?;
+
+        room_state.members.push(Member {
+            id,
+            name: None,
+            outgoing: outgoing.clone(),
+        });
+        let _ = outgoing.send(ServerMessage::Watching {
+            room: room.to_string(),
+        });
+        let _ = outgoing.send(ServerMessage::StateUpdate {
+            message: format!("Watching {}.", room),
+            state: GameView::for_spectators(&room_state.game_state),
+        });
+        Ok(())
+    }
+
+    /// Act on a message from a client in a room.
+    ///
+    /// # Arguments
+    ///
+    /// * `room` - The name of the client's room.
+    /// * `id` - The number of the client's connection.
+    /// * `message` - The client's message.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - An error for the client if the message can't be acted on.
+    fn play(&mut self, room: &str, id: usize, message: ClientMessage) -> Result<(), String> {
+        let room = self.rooms.get_mut(room).ok_or("The room is gone.")?;
+        let member = (room.members.iter().find(|member| member.id == id))
+            .ok_or("You are not in the room.")?;
+        let name = member.name.clone().ok_or("Spectators can only watch.")?;
+
+        match message {
+            ClientMessage::Guess { .. } | ClientMessage::Solve { .. } => {
+                let game_state = &mut room.game_state;
+                if game_state.is_over() {
+                    return Err("The game is over. Start a new game.".to_string());
+                }
+                if let Some(player) = game_state.current_player() {
+                    if player.name != name {
+                        return Err(format!("It's {}'s turn.", player.name));
+                    }
+                }
+
+                let outcome = match message {
+                    ClientMessage::Guess { letter } => {
+                        game_state.guess_letter(letter).message(letter)
+                    }
+                    ClientMessage::Solve { attempt } => {
+                        game_state.solve_phrase(&attempt).message().to_string()
+                    }
+                    _ => unreachable!("Only guesses and solve attempts get here"),
+                };
+                room.send_state(format!("{}: {}", name, outcome));
+                if room.game_state.is_over() {
+                    room.announce_game_over();
+                }
+            }
+            ClientMessage::Chat { text } => {
+                room.broadcast(ServerMessage::Chat { from: name, text })
+            }
+            ClientMessage::NewGame => {
+                if !room.game_state.is_over() {
+                    return Err("The game isn't over yet.".to_string());
+                }
+                room.new_game();
+                room.send_state(format!("{} started a new game.", name));
+            }
+            ClientMessage::Join { .. } | ClientMessage::Watch { .. } => {
+                return Err("You are already in a room.".to_string())
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a client that disconnected from its room, and the room once it is empty. The
+    /// players left are told when a player leaves.
+    ///
+    /// # Arguments
+    ///
+    /// * `room` - The name of the client's room.
+    /// * `id` - The number of the client's connection.
+    ///
+    fn leave(&mut self, room: &str, id: usize) {
+        let Some(room_state) = self.rooms.get_mut(room) else {
+            return;
+        };
+        let Some(position) = room_state.members.iter().position(|member| member.id == id) else {
+            return;
+        };
+
+        let member = room_state.members.remove(position);
+        if room_state.members.is_empty() {
+            self.rooms.remove(room);
+            return;
+        }
+        let Some(name) = member.name else {
+            return;
+        };
+        let game_state = &room_state.game_state;
+        if let Some(player) = (game_state.players().iter()).position(|player| player.name == name) {
+            room_state.game_state.player_left(player);
+        }
+        room_state.send_state(format!("{} left.", name));
+    }
+}
+
+impl Room {
+    /// Queue a message for every client in the room.
+    fn broadcast(&self, message: ServerMessage) {
+        for member in &self.members {
+            let _ = member.outgoing.send(message.clone());
+        }
+    }
+
+    /// Send the state of the room's game to every client in it, without the phrase for spectators.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - What changed.
+    ///
+    fn send_state(&self, message: String) {
+        let player_view = GameView::from_game(&self.game_state);
+        let spectator_view = GameView::for_spectators(&self.game_state);
+        for member in &self.members {
+            let state = match member.name {
+                Some(_) => player_view.clone(),
+                None => spectator_view.clone(),
+            };
+            let _ = member.outgoing.send(ServerMessage::StateUpdate {
+                message: message.clone(),
+                state,
+            });
+        }
+    }
+
+    /// Tell the players in the room how the game ended. Spectators see it in the state, but
+    /// are not told the phrase.
+    fn announce_game_over(&self) {
+        let game_state = &self.game_state;
+        let winners = if game_state.is_won() {
+            game_state
+                .leaders()
+                .iter()
+                .map(|player| player.name.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let message = ServerMessage::GameOver {
+            win: game_state.is_won(),
+            phrase: game_state.phrase().to_string(),
+            winners,
+        };
+        for member in self.members.iter().filter(|member| member.name.is_some()) {
+            let _ = member.outgoing.send(message.clone());
+        }
+    }
+
+    /// Set up a new game, at the same difficulty, for the players in the room. It starts with
+    /// a random phrase on its first guess, and players who join before then play in it too.
+    fn new_game(&mut self) {
+        self.game_state = self
+            .game_state
+            .next_game()
+            .players(self.members.iter().filter_map(|member| member.name.clone()))
+            .build();
+    }
+}
@@ -0,0 +1,498 @@
+//! Terminal front-end for Rusty Hangman, for playing over SSH or on headless machines.
+//!
+//! Drives the same `HangmanGameState` engine as the eframe GUI.
+
+use std::io::{self, Stdout, Write};
+use std::sync::Arc;
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+use rusty_hangman::cli::{CliOptions, USAGE};
+use rusty_hangman::{
+    category_display_name, embedded_word_source, open_word_source, Difficulty, GallowsPart,
+    GameSettings, HangmanGameState, Language, PuzzleCode, SolvePenalty, WordSource,
+};
+
+/// The width and height of the ASCII drawing the gallows parts are filled into.
+const CANVAS_SIZE: (usize, usize) = (9, 7);
+
+/// The characters that draw a part of the gallows or the hangman: (row, column, character).
+fn part_cells(part: GallowsPart) -> &'static [(usize, usize, char)] {
+    match part {
+        GallowsPart::Base => &[
+            (6, 0, '='),
+            (6, 1, '='),
+            (6, 2, '='),
+            (6, 3, '='),
+            (6, 4, '='),
+        ],
+        GallowsPart::Upright => &[
+            (1, 2, '|'),
+            (2, 2, '|'),
+            (3, 2, '|'),
+            (4, 2, '|'),
+            (5, 2, '|'),
+        ],
+        GallowsPart::Beam => &[
+            (0, 2, '+'),
+            (0, 3, '-'),
+            (0, 4, '-'),
+            (0, 5, '-'),
+            (0, 6, '+'),
+        ],
+        GallowsPart::Rope => &[(1, 6, '|')],
+        GallowsPart::Head => &[(2, 6, 'O')],
+        GallowsPart::Body => &[(3, 6, '|')],
+        GallowsPart::LeftArm => &[(3, 5, '/')],
+        GallowsPart::RightArm => &[(3, 7, '\\')],
+        GallowsPart::LeftLeg => &[(4, 5, '/')],
+        GallowsPart::RightLeg => &[(4, 7, '\\')],
+    }
+}
+
+/// What the player chose to do after a round has finished.
+enum RoundEnd {
+    Restart,
+    Quit,
+}
+
+/// The choices made on the difficulty menu that carry over between rounds.
+struct Settings {
+    /// The word source passed with `--words`, used instead of the embedded words.
+    custom_word_source: Option<Arc<dyn WordSource>>,
+    /// The language whose alphabet is played with.
+    language: Language,
+    /// The category words are picked from, or `None` for any word.
+    category: Option<String>,
+    /// Whether guessing a letter also reveals its accented forms that are not in the alphabet.
+    fold_accents: bool,
+    /// What a wrong attempt at solving the whole phrase costs.
+    solve_penalty: SolvePenalty,
+    /// The seed given with `--seed`, which the next game's phrase is picked with.
+    seed: Option<u64>,
+    /// The shared puzzle given with `--puzzle`, which is played first without the menu.
+    puzzle: Option<PuzzleCode>,
+}
+
+impl Settings {
+    /// The source words are picked from: the user's own if given, the language's embedded words otherwise.
+    fn word_source(&self) -> Arc<dyn WordSource> {
+        match &self.custom_word_source {
+            Some(word_source) => word_source.clone(),
+            None => embedded_word_source(self.language),
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let cli_options = match CliOptions::from_env() {
+        Ok(cli_options) => cli_options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli_options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let custom_word_source = match &cli_options.words {
+        Some(path) => match open_word_source(path) {
+            Ok(word_source) => Some(word_source),
+            Err(error) => {
+                eprintln!("Could not load {}: {}", path.display(), error);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let mut settings = Settings {
+        custom_word_source,
+        language: Language::default(),
+        category: None,
+        fold_accents: Language::default().folds_accents(),
+        solve_penalty: SolvePenalty::default(),
+        seed: cli_options.seed,
+        puzzle: cli_options.puzzle,
+    };
+
+    let mut stdout = io::stdout();
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = run(&mut stdout, &mut settings);
+
+    // Always restore the terminal, even if the game loop failed.
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+
+    result
+}
+
+/// Run rounds of the game until the player quits.
+fn run(stdout: &mut Stdout, settings: &mut Settings) -> io::Result<()> {
+    loop {
+        let builder = match settings.puzzle.take() {
+            Some(puzzle) => {
+                // Later rounds keep the puzzle's settings on the menu
+                settings.language = puzzle.settings.language;
+                settings.category = puzzle.settings.category.clone();
+                settings.fold_accents = puzzle.settings.fold_accents;
+                settings.solve_penalty = puzzle.settings.solve_penalty;
+                HangmanGameState::builder().puzzle(&puzzle)
+            }
+            None => {
+                let Some(difficulty) = select_difficulty(stdout, settings)? else {
+                    return Ok(());
+                };
+                HangmanGameState::builder().settings(GameSettings {
+                    difficulty: Some(difficulty),
+                    category: settings.category.clone(),
+                    language: settings.language,
+                    fold_accents: settings.fold_accents,
+                    solve_penalty: settings.solve_penalty,
+                    ..Default::default()
+                })
+            }
+        };
+
+        let mut builder = builder.word_source(settings.word_source());
+        if let Some(seed) = settings.seed.take() {
+            builder = builder.seed(seed);
+        }
+        let mut game_state = builder.build();
+        game_state.start()?;
+
+        match play(stdout, &mut game_state)? {
+            RoundEnd::Restart => continue,
+            RoundEnd::Quit => return Ok(()),
+        }
+    }
+}
+
+/// Show the difficulty menu and wait for a choice, letting the player cycle through languages
+/// and categories.
+///
+/// # Arguments
+///
+/// * `settings` - The language and category, updated as the player cycles through them.
+///
+/// # Returns
+///
+/// * `io::Result<Option<Difficulty>>` - The selected difficulty, or `None` if the player quit.
+fn select_difficulty(
+    stdout: &mut Stdout,
+    settings: &mut Settings,
+) -> io::Result<Option<Difficulty>> {
+    loop {
+        let word_source = settings.word_source();
+        let categories = word_source.categories();
+        draw_difficulty_menu(stdout, settings)?;
+
+        match read_key()? {
+            KeyCode::Char('c') | KeyCode::Char('C') if !categories.is_empty() => {
+                // Cycle Any -> first category -> ... -> last category -> Any
+                let next = match &settings.category {
+                    Some(current) => categories
+                        .iter()
+                        .position(|c| c == current)
+                        .map_or(0, |position| position + 1),
+                    None => 0,
+                };
+                settings.category = categories.get(next).cloned();
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                let position = Language::ALL
+                    .iter()
+                    .position(|language| *language == settings.language)
+                    .unwrap_or(0);
+                settings.language = Language::ALL[(position + 1) % Language::ALL.len()];
+                settings.fold_accents = settings.language.folds_accents();
+                // The embedded words change with the language, and their categories with them
+                if settings.custom_word_source.is_none() {
+                    settings.category = None;
+                }
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                settings.fold_accents = !settings.fold_accents;
+            }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                let position = SolvePenalty::CHOICES
+                    .iter()
+                    .position(|penalty| *penalty == settings.solve_penalty)
+                    .map_or(0, |position| position + 1);
+                settings.solve_penalty =
+                    SolvePenalty::CHOICES[position % SolvePenalty::CHOICES.len()];
+            }
+            KeyCode::Char(c) => {
+                let choice = c.to_digit(10).and_then(|n| n.checked_sub(1));
+                if let Some(&difficulty) = choice.and_then(|n| Difficulty::ALL.get(n as usize)) {
+                    return Ok(Some(difficulty));
+                }
+            }
+            KeyCode::Esc => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Draw the difficulty menu.
+fn draw_difficulty_menu(stdout: &mut Stdout, settings: &Settings) -> io::Result<()> {
+    let word_source = settings.word_source();
+
+    queue!(
+        stdout,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0),
+        Print("Rusty Hangman Game!\r\n\r\n"),
+        Print(format!("Word source: {}\r\n", word_source.name())),
+        Print(format!(
+            "Language: {} (press L to change)\r\n",
+            settings.language.name()
+        )),
+        Print(format!(
+            "Ignore accents: {} (press A to change)\r\n",
+            if settings.fold_accents { "on" } else { "off" }
+        )),
+        Print(format!(
+            "Wrong solve: {} (press P to change)\r\n",
+            settings.solve_penalty.name()
+        )),
+    )?;
+    if !word_source.categories().is_empty() {
+        queue!(
+            stdout,
+            Print(format!(
+                "Category: {} (press C to change)\r\n",
+                settings
+                    .category
+                    .as_deref()
+                    .map_or("Any".to_string(), category_display_name)
+            )),
+        )?;
+    }
+    queue!(stdout, Print("\r\nSelect difficulty:\r\n"))?;
+    for (number, difficulty) in Difficulty::ALL.iter().enumerate() {
+        let profile = difficulty.profile();
+        queue!(
+            stdout,
+            Print(format!(
+                "  {}) {} - {} misses allowed, {}-{} letter words, {} hints\r\n",
+                number + 1,
+                difficulty.name(),
+                profile.allowed_misses,
+                profile.words.length.start(),
+                profile.words.length.end(),
+                profile.hint_budget
+            )),
+        )?;
+    }
+    queue!(stdout, Print("\r\nPress Esc to quit.\r\n"))?;
+    stdout.flush()
+}
+
+/// Play a single round until it is won or lost, or the player quits.
+fn play(stdout: &mut Stdout, game_state: &mut HangmanGameState) -> io::Result<RoundEnd> {
+    // What happened to the last guess, shown under the phrase
+    let mut message = String::new();
+
+    loop {
+        draw(stdout, game_state, &message)?;
+
+        if game_state.is_over() {
+            return match read_key()? {
+                KeyCode::Char('r') | KeyCode::Char('R') => Ok(RoundEnd::Restart),
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => Ok(RoundEnd::Quit),
+                _ => continue,
+            };
+        }
+
+        match read_key()? {
+            KeyCode::Enter => {
+                if let Some(attempt) = read_solve_attempt(stdout, game_state)? {
+                    message = game_state.solve_phrase(&attempt).message().to_string();
+                }
+            }
+            KeyCode::Char('?') => {
+                message = match game_state.use_hint() {
+                    Some(letter) => format!("Hint: {} is in the phrase.", letter),
+                    None => "No hints left.".to_string(),
+                };
+            }
+            // Any character is passed on, so accented letters can be typed when accents aren't
+            // ignored; the game state rejects the ones that can't be guessed
+            KeyCode::Char(c) => {
+                message = game_state.guess_letter(c).message(c);
+            }
+            KeyCode::Esc => return Ok(RoundEnd::Quit),
+            _ => {}
+        }
+    }
+}
+
+/// Let the player type an attempt at the whole phrase below the game.
+///
+/// # Returns
+///
+/// * `io::Result<Option<String>>` - The attempt, or `None` if the player cancelled or typed nothing.
+fn read_solve_attempt(
+    stdout: &mut Stdout,
+    game_state: &HangmanGameState,
+) -> io::Result<Option<String>> {
+    let mut attempt = String::new();
+
+    loop {
+        draw(
+            stdout,
+            game_state,
+            &format!(
+                "Solve the phrase (Enter to submit, Esc to cancel): {}_",
+                attempt
+            ),
+        )?;
+
+        match read_key()? {
+            KeyCode::Enter if attempt.trim().is_empty() => return Ok(None),
+            KeyCode::Enter => return Ok(Some(attempt)),
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Backspace => {
+                attempt.pop();
+            }
+            KeyCode::Char(c) => attempt.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Draw the gallows, the obfuscated phrase, the guessed-letter alphabet and the outcome of the last guess.
+fn draw(stdout: &mut Stdout, game_state: &HangmanGameState, message: &str) -> io::Result<()> {
+    queue!(
+        stdout,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0),
+        Print("Rusty Hangman Game!\r\n\r\n"),
+    )?;
+
+    for row in gallows(game_state) {
+        queue!(stdout, Print(row), Print("\r\n"))?;
+    }
+
+    queue!(
+        stdout,
+        Print(format!(
+            "\r\nPhrase to guess: {}\r\n",
+            game_state.obfuscated_phrase()
+        )),
+    )?;
+    if let Some(category) = game_state.category() {
+        queue!(
+            stdout,
+            Print(format!("Category: {}\r\n", category_display_name(category))),
+        )?;
+    }
+    if let Some(code) = game_state.puzzle_code() {
+        queue!(
+            stdout,
+            Print(format!(
+                "Seed: {}    Puzzle code: {}\r\n",
+                game_state.seed(),
+                code
+            )),
+        )?;
+    }
+    if !message.is_empty() {
+        queue!(stdout, Print(format!("\r\n{}\r\n", message)))?;
+    }
+    queue!(stdout, Print("\r\nGuesses: "))?;
+
+    // Same colouring as the GUI: green for hits, red for misses, default for untried letters.
+    for letter in game_state.language().alphabet() {
+        if game_state.chars_to_guess().contains(letter)
+            && game_state.guessed_letters().contains(letter)
+        {
+            queue!(stdout, SetForegroundColor(Color::Green))?;
+        } else if game_state.guessed_letters().contains(letter) {
+            queue!(stdout, SetForegroundColor(Color::Red))?;
+        }
+        queue!(stdout, Print(format!("{} ", letter)), ResetColor)?;
+    }
+
+    queue!(
+        stdout,
+        Print(format!(
+            "\r\n\r\nGuesses Left: {}    Hints Left: {}\r\n\r\n",
+            game_state.guesses_left(),
+            game_state.hints_left()
+        )),
+    )?;
+
+    if game_state.is_over() {
+        if game_state.is_won() {
+            queue!(stdout, Print("Congratulations! You won!\r\n"))?;
+        } else {
+            queue!(stdout, Print("Game Over! You lost!\r\n"))?;
+        }
+        queue!(
+            stdout,
+            Print(format!(
+                "The phrase was: {}\r\nScore: {}\r\n\r\nPress R to restart or Q to quit.\r\n",
+                game_state.phrase(),
+                game_state.score().total
+            )),
+        )?;
+    } else {
+        queue!(
+            stdout,
+            Print("Type a letter to guess, ? for a hint, Enter to solve the phrase. Press Esc to quit.\r\n")
+        )?;
+    }
+
+    stdout.flush()
+}
+
+/// Build the gallows and the hangman with as many parts as the incorrect guesses so far allow.
+///
+/// The parts are spread over the allowed misses, so the drawing is complete exactly when the
+/// game is lost, whatever the difficulty. A lost hangman gets an `X` for a head.
+fn gallows(game_state: &HangmanGameState) -> Vec<String> {
+    let (width, height) = CANVAS_SIZE;
+    let mut rows = vec![vec![' '; width]; height];
+
+    for &part in game_state.gallows_parts() {
+        for &(row, column, c) in part_cells(part) {
+            rows[row][column] = c;
+        }
+    }
+    if game_state.is_over() && !game_state.is_won() {
+        rows[2][6] = 'X';
+    }
+
+    rows.into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect()
+}
+
+/// Block until a key is pressed. Ctrl+C is reported as Esc since raw mode swallows the signal.
+fn read_key() -> io::Result<KeyCode> {
+    loop {
+        if let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        {
+            if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
+                return Ok(KeyCode::Esc);
+            }
+            return Ok(code);
+        }
+    }
+}
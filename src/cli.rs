@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use crate::puzzle::PuzzleCode;
+
+/// Usage text for the command line options shared by the front-ends.
+pub const USAGE: &str = "Options:
+    --words <PATH>    Pick words from a word file or a directory of category files
+    --server <ADDR>   The multiplayer server address to listen on, or for the GUI to join
+    --seed <N>        Pick the first game's phrase with this seed, to play it again
+    --puzzle <CODE>   Start with the puzzle a share code describes
+    -h, --help        Show this help";
+
+/// Command line options shared by the front-ends.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CliOptions {
+    /// The word file or directory of category files to pick words from, if not the embedded list.
+    pub words: Option<PathBuf>,
+    /// The address of the multiplayer server, if not the default.
+    pub server: Option<String>,
+    /// The seed to pick the first game's phrase with, if not a random one.
+    pub seed: Option<u64>,
+    /// The shared puzzle to play first, if any.
+    pub puzzle: Option<PuzzleCode>,
+    /// Flag indicating if the usage text was asked for.
+    pub help: bool,
+}
+
+impl CliOptions {
+    /// Parse the options from the command line arguments of the current process.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<CliOptions, String>` - The parsed options, or a message describing the invalid argument.
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    /// Parse the options from a list of arguments, not including the program name.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The arguments to parse.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<CliOptions, String>` - The parsed options, or a message describing the invalid argument.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = CliOptions::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--words" => {
+                    let path = args.next().ok_or("--words needs a path")?;
+                    options.words = Some(PathBuf::from(path));
+                }
+                "--server" => {
+                    let address = args.next().ok_or("--server needs an address")?;
+                    options.server = Some(address);
+                }
+                "--seed" => {
+                    let seed = args.next().ok_or("--seed needs a number")?;
+                    options.seed = Some(parse_seed(&seed)?);
+                }
+                "--puzzle" => {
+                    let code = args.next().ok_or("--puzzle needs a code")?;
+                    options.puzzle = Some(code.parse()?);
+                }
+                "-h" | "--help" => options.help = true,
+                _ => {
+                    if let Some(path) = arg.strip_prefix("--words=") {
+                        options.words = Some(PathBuf::from(path));
+                    } else if let Some(address) = arg.strip_prefix("--server=") {
+                        options.server = Some(address.to_string());
+                    } else if let Some(seed) = arg.strip_prefix("--seed=") {
+                        options.seed = Some(parse_seed(seed)?);
+                    } else if let Some(code) = arg.strip_prefix("--puzzle=") {
+                        options.puzzle = Some(code.parse()?);
+                    } else {
+                        return Err(format!("Unknown argument: {}", arg));
+                    }
+                }
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Parse a seed given on the command line.
+///
+/// # Arguments
+///
+/// * `seed` - The seed, as a decimal number.
+///
+/// # Returns
+///
+/// * `Result<u64, String>` - The seed, or a message saying it is not a number.
+fn parse_seed(seed: &str) -> Result<u64, String> {
+    seed.trim()
+        .parse()
+        .map_err(|_| format!("--seed needs a number, not {}", seed))
+}
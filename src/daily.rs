@@ -0,0 +1,208 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alphabet::Language;
+use crate::difficulty::Difficulty;
+use crate::hangmangame::{GameSettings, HangmanGameState, Move};
+use crate::wordsource::WordSource;
+
+/// The difficulty every daily puzzle is played on, so everyone gets the same puzzle.
+pub const DAILY_DIFFICULTY: Difficulty = Difficulty::Normal;
+
+/// How many moves are shown on each row of a result summary.
+const SUMMARY_ROW_LENGTH: usize = 5;
+
+/// A calendar day in UTC, so a daily puzzle changes at the same moment for everyone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Day(pub u32);
+
+/// A profile's attempt at a daily puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyAttempt {
+    /// The day of the puzzle.
+    pub day: Day,
+    /// The result summary to share, or `None` while the puzzle is being played.
+    pub summary: Option<String>,
+}
+
+impl Day {
+    /// The day it is now.
+    pub fn today() -> Self {
+        Day::from_time(SystemTime::now())
+    }
+
+    /// The day a moment falls on.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The moment.
+    ///
+    pub fn from_time(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Day((seconds / 86_400) as u32)
+    }
+
+    /// The year, month and day of the month, in the Gregorian calendar.
+    pub fn date(&self) -> (i64, u32, u32) {
+        // Days since 1970-01-01 to a civil date, counting in 400-year eras that start on 1 March
+        let days = i64::from(self.0) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month as u32, day as u32)
+    }
+}
+
+impl fmt::Display for Day {
+    /// Write the day as `YYYY-MM-DD`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.date();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// The seed a day's puzzle is picked with. It depends on the day and on the words of the word
+/// source, so players with the same words get the same puzzle wherever the words were loaded from.
+///
+/// # Arguments
+///
+/// * `day` - The day of the puzzle.
+/// * `word_source` - The source the phrase is picked from.
+///
+/// # Returns
+///
+/// * `u64` - The seed.
+pub fn daily_seed(day: Day, word_source: &dyn WordSource) -> u64 {
+    // FNV-1a, which unlike the standard library's hasher is the same on every machine and version
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut add = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    for word in word_source.index().words() {
+        add(word.as_bytes());
+        add(b"\n");
+    }
+    add(&day.0.to_le_bytes());
+    hash
+}
+
+/// The settings every daily puzzle is played with, in a language.
+///
+/// # Arguments
+///
+/// * `language` - The language the player plays in.
+///
+/// # Returns
+///
+/// * `GameSettings` - The settings.
+pub fn daily_settings(language: Language) -> GameSettings {
+    GameSettings {
+        difficulty: Some(DAILY_DIFFICULTY),
+        language,
+        fold_accents: language.folds_accents(),
+        ..Default::default()
+    }
+}
+
+/// A summary of how a daily puzzle went, to share without giving the phrase away: a title
+/// line with the day and the result, then the moves in order as a grid of emoji (a green
+/// square for a hit, red for a miss, yellow for a hint, a star for solving the phrase and a
+/// cross for a wrong solve).
+///
+/// # Arguments
+///
+/// * `game_state` - The daily puzzle's game.
+///
+/// # Returns
+///
+/// * `Option<String>` - The summary, or `None` if the game is not a finished daily puzzle.
+pub fn daily_summary(game_state: &HangmanGameState) -> Option<String> {
+    let day = game_state.daily()?;
+    if !game_state.is_over() {
+        return None;
+    }
+
+    let result = if game_state.is_won() {
+        format!(
+            "solved with {}/{} misses",
+            game_state.incorrect_guess_count(),
+            game_state.allowed_misses()
+        )
+    } else {
+        "not solved".to_string()
+    };
+    let mut summary = format!("Rusty Hangman daily {}: {}", day, result);
+    for row in game_state.moves().chunks(SUMMARY_ROW_LENGTH) {
+        summary.push('\n');
+        summary.extend(row.iter().map(|played| match played {
+            Move::Hit => '🟩',
+            Move::Miss => '🟥',
+            Move::Hint => '🟨',
+            Move::Solved => '⭐',
+            Move::WrongSolve => '❌',
+        }));
+    }
+    Some(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordsource::WordList;
+
+    fn words(name: &str) -> WordList {
+        WordList::new(name, vec!["cat".to_string(), "dog".to_string()]).unwrap()
+    }
+
+    #[test]
+    fn days_become_dates() {
+        assert_eq!(Day(0).to_string(), "1970-01-01");
+        assert_eq!(Day(11_016).to_string(), "2000-02-29");
+        assert_eq!(Day(47_540).to_string(), "2100-02-28");
+        assert_eq!(Day(47_541).to_string(), "2100-03-01");
+        assert_eq!(Day(20_744).to_string(), "2026-10-18");
+    }
+
+    #[test]
+    fn days_start_at_midnight_utc() {
+        let day = |seconds| Day::from_time(UNIX_EPOCH + std::time::Duration::from_secs(seconds));
+        assert_eq!(day(86_399), Day(0));
+        assert_eq!(day(86_400), Day(1));
+    }
+
+    #[test]
+    fn daily_seed_is_stable() {
+        // Changing this value changes every player's daily puzzle
+        assert_eq!(
+            daily_seed(Day(20_744), &words("mine")),
+            0x27b2_b2cc_41b8_990c
+        );
+    }
+
+    #[test]
+    fn daily_seed_depends_on_the_words_and_day_only() {
+        let seed = daily_seed(Day(20_744), &words("mine"));
+        assert_eq!(daily_seed(Day(20_744), &words("theirs")), seed);
+        assert_ne!(daily_seed(Day(20_745), &words("mine")), seed);
+        let other = WordList::new("mine", vec!["cat".to_string()]).unwrap();
+        assert_ne!(daily_seed(Day(20_744), &other), seed);
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::alphabet::Language;
+
+/// How often each letter A-Z appears in English text, in tenths of a percent.
+const ENGLISH_LETTER_FREQUENCY: [u32; 26] = [
+    82, 15, 28, 43, 127, 22, 20, 61, 70, 2, 8, 40, 24, 67, 75, 19, 1, 60, 63, 91, 28, 10, 24, 2,
+    20, 1,
+];
+
+/// The highest score `letter_frequency_score` can return (a word made only of `E`s).
+pub const MAX_LETTER_FREQUENCY_SCORE: u32 = 127;
+
+/// The difficulty levels of the Hangman game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    VeryEasy,
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+/// The rules a difficulty level is played with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DifficultyProfile {
+    /// The number of incorrect guesses allowed before the game is lost.
+    pub allowed_misses: u32,
+    /// The words the game picks from.
+    pub words: WordFilter,
+    /// The number of hints the player may use in a game.
+    pub hint_budget: u32,
+    /// The percentage a game's score is multiplied by, so harder games score more.
+    pub score_multiplier: u32,
+}
+
+/// Parameters for picking a word, independent of how many misses are allowed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WordFilter {
+    /// The allowed word lengths, in grapheme clusters (see `word_length`).
+    pub length: RangeInclusive<usize>,
+    /// The allowed numbers of distinct letters in the word (see `unique_letter_count`).
+    pub unique_letters: RangeInclusive<usize>,
+    /// The allowed band of `letter_frequency_score`s.
+    ///
+    /// The embedded word list carries no per-word frequencies, so how common a word's letters
+    /// are in English stands in for how common the word is. See `for_language` for other
+    /// languages.
+    pub frequency_band: RangeInclusive<u32>,
+}
+
+impl Difficulty {
+    /// Every difficulty, from easiest to hardest.
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::VeryEasy,
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+    ];
+
+    /// The name of the difficulty, as shown to the player.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::VeryEasy => "Very Easy",
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    /// The rules this difficulty is played with.
+    pub fn profile(&self) -> DifficultyProfile {
+        match self {
+            Difficulty::VeryEasy => DifficultyProfile {
+                allowed_misses: 10,
+                words: WordFilter {
+                    length: 6..=10,
+                    frequency_band: 63..=MAX_LETTER_FREQUENCY_SCORE,
+                    ..WordFilter::any()
+                },
+                hint_budget: 3,
+                score_multiplier: 100,
+            },
+            Difficulty::Easy => DifficultyProfile {
+                allowed_misses: 8,
+                words: WordFilter {
+                    length: 5..=9,
+                    frequency_band: 55..=MAX_LETTER_FREQUENCY_SCORE,
+                    ..WordFilter::any()
+                },
+                hint_budget: 2,
+                score_multiplier: 150,
+            },
+            Difficulty::Normal => DifficultyProfile {
+                allowed_misses: 6,
+                words: WordFilter {
+                    length: 5..=8,
+                    ..WordFilter::any()
+                },
+                hint_budget: 1,
+                score_multiplier: 200,
+            },
+            Difficulty::Hard => DifficultyProfile {
+                allowed_misses: 4,
+                words: WordFilter {
+                    length: 4..=7,
+                    frequency_band: 0..=58,
+                    ..WordFilter::any()
+                },
+                hint_budget: 0,
+                score_multiplier: 300,
+            },
+        }
+    }
+}
+
+impl WordFilter {
+    /// Create a filter that accepts every word.
+    pub fn any() -> Self {
+        WordFilter {
+            length: 0..=usize::MAX,
+            unique_letters: 0..=usize::MAX,
+            frequency_band: 0..=MAX_LETTER_FREQUENCY_SCORE,
+        }
+    }
+
+    /// The filter to pick words in a language with. `letter_frequency_score` only knows how
+    /// common letters are in English, and would put most words of other alphabets in the
+    /// lowest band, so outside English every frequency band is accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language of the game the words are picked for.
+    ///
+    pub fn for_language(self, language: Language) -> Self {
+        if language == Language::English {
+            self
+        } else {
+            WordFilter {
+                frequency_band: 0..=MAX_LETTER_FREQUENCY_SCORE,
+                ..self
+            }
+        }
+    }
+
+    /// Check whether no word can pass the filter, because one of its ranges is empty.
+    pub fn is_empty(&self) -> bool {
+        self.length.is_empty() || self.unique_letters.is_empty() || self.frequency_band.is_empty()
+    }
+
+    /// Check whether a word passes the filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `word` - The word to check.
+    ///
+    pub fn matches(&self, word: &str) -> bool {
+        self.length.contains(&word_length(word))
+            && self.unique_letters.contains(&unique_letter_count(word))
+            && self.frequency_band.contains(&letter_frequency_score(word))
+    }
+}
+
+/// The length of a word, in grapheme clusters, so a letter with combining marks counts once.
+///
+/// # Arguments
+///
+/// * `word` - The word to measure.
+///
+pub fn word_length(word: &str) -> usize {
+    word.graphemes(true).count()
+}
+
+/// The number of distinct letters in a word, ignoring case.
+///
+/// # Arguments
+///
+/// * `word` - The word to count the letters of.
+///
+pub fn unique_letter_count(word: &str) -> usize {
+    word.chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_uppercase)
+        .collect::<HashSet<char>>()
+        .len()
+}
+
+/// Score how common a word's letters are in English, from 0 to `MAX_LETTER_FREQUENCY_SCORE`.
+///
+/// The score is the mean English frequency of the word's letters, so words made of letters
+/// like `E`, `T` and `A` score high and words with `Q`, `Z` or `J` score low. Characters
+/// outside A-Z count as 0.
+///
+/// # Arguments
+///
+/// * `word` - The word to score.
+///
+pub fn letter_frequency_score(word: &str) -> u32 {
+    let mut total = 0;
+    let mut count = 0;
+
+    for c in word.chars() {
+        let c = c.to_ascii_uppercase();
+        if c.is_ascii_uppercase() {
+            total += ENGLISH_LETTER_FREQUENCY[(c as u8 - b'A') as usize];
+        }
+        count += 1;
+    }
+
+    total.checked_div(count).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordsource::embedded_word_source;
+
+    #[test]
+    fn every_language_has_words_on_every_difficulty() {
+        for language in Language::ALL {
+            let word_source = embedded_word_source(language);
+            for difficulty in Difficulty::ALL {
+                let filter = difficulty.profile().words.for_language(language);
+                assert!(
+                    word_source.index().count_matching(&filter) > 0,
+                    "no {} words on {}",
+                    language.name(),
+                    difficulty.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn frequency_band_is_kept_in_english_only() {
+        let filter = Difficulty::Hard.profile().words;
+        assert_eq!(filter.clone().for_language(Language::English), filter);
+        assert_eq!(
+            filter.for_language(Language::Greek).frequency_band,
+            0..=MAX_LETTER_FREQUENCY_SCORE
+        );
+    }
+}
@@ -0,0 +1,72 @@
+/// The parts of the gallows and the hangman, in the order they are drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GallowsPart {
+    Base,
+    Upright,
+    Beam,
+    Rope,
+    Head,
+    Body,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+}
+
+impl GallowsPart {
+    /// Every part, in the order they are drawn: the gallows first, then the hangman.
+    pub const ALL: [GallowsPart; 10] = [
+        GallowsPart::Base,
+        GallowsPart::Upright,
+        GallowsPart::Beam,
+        GallowsPart::Rope,
+        GallowsPart::Head,
+        GallowsPart::Body,
+        GallowsPart::LeftArm,
+        GallowsPart::RightArm,
+        GallowsPart::LeftLeg,
+        GallowsPart::RightLeg,
+    ];
+}
+
+/// The number of gallows parts before the hangman in `GallowsPart::ALL`.
+const GALLOWS_PART_COUNT: usize = 4;
+
+/// How many hangman parts are drawn after each of the stages used when fewer misses are allowed
+/// than there are hangman parts: the head, the body, both arms, then both legs.
+const SHORT_HANGMAN_STAGES: [usize; 4] = [1, 2, 4, 6];
+
+/// The parts to draw after a number of incorrect guesses.
+///
+/// Every allowed miss adds a stage, so the drawing is complete exactly when the game is lost:
+/// with 10 allowed misses the gallows is built one part at a time, with 6 to 9 the first parts
+/// of the gallows are there from the start, and with fewer than 6 the whole gallows is there
+/// and the hangman's arms and legs are added in pairs. Nothing is drawn before a difficulty,
+/// and with it the number of allowed misses, has been chosen.
+///
+/// # Arguments
+///
+/// * `incorrect_guess_count` - The number of incorrect guesses so far.
+/// * `allowed_misses` - The number of incorrect guesses allowed before the game is lost.
+///
+pub fn visible_parts(incorrect_guess_count: u32, allowed_misses: u32) -> &'static [GallowsPart] {
+    let part_count = GallowsPart::ALL.len();
+    let allowed = allowed_misses as usize;
+    let misses = (incorrect_guess_count as usize).min(allowed);
+
+    let count = if allowed == 0 {
+        0
+    } else if allowed >= part_count {
+        (misses * part_count).div_ceil(allowed)
+    } else if allowed >= part_count - GALLOWS_PART_COUNT {
+        part_count - allowed + misses
+    } else {
+        let stages = SHORT_HANGMAN_STAGES.len();
+        match (misses * stages).div_ceil(allowed) {
+            0 => GALLOWS_PART_COUNT,
+            stage => GALLOWS_PART_COUNT + SHORT_HANGMAN_STAGES[stage.min(stages) - 1],
+        }
+    };
+
+    &GallowsPart::ALL[..count.min(part_count)]
+}
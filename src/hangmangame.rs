@@ -1,113 +1,966 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fs;
 use std::io::{self};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::alphabet::{
+    char_class, graphemes_with_letters, guess_key, normalize_letter, normalize_phrase, AutoReveal,
+    CharClass, Language,
+};
+use crate::daily::{daily_seed, daily_settings, Day};
+use crate::difficulty::{Difficulty, DifficultyProfile, WordFilter};
+use crate::gallows::{visible_parts, GallowsPart};
+use crate::net::GameView;
+use crate::players::{MissLimit, Player};
+use crate::puzzle::PuzzleCode;
+use crate::wordsource::{EmbeddedWordList, WordSource};
+
+/// Points scored for each letter of the phrase that has been revealed.
+const POINTS_PER_LETTER: u32 = 10;
+
+/// Points scored on a win for each incorrect guess the player had left.
+const POINTS_PER_MISS_LEFT: u32 = 25;
+
+/// A win within this many seconds scores a point for every second left.
+const TIME_BONUS_SECONDS: u64 = 120;
+
+/// What the player is told when no phrase can be picked for the game.
+const NO_PHRASE_MESSAGE: &str = "None of the words can be played with these settings.";
+
+/// How a game's score is made up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Score {
+    /// Points for the letters of the phrase revealed so far.
+    pub letter_points: u32,
+    /// Points for the incorrect guesses left, only on a win.
+    pub misses_bonus: u32,
+    /// Points for winning quickly.
+    pub time_bonus: u32,
+    /// The percentage the points are multiplied by for the difficulty.
+    pub multiplier: u32,
+    /// The points, multiplied by the difficulty multiplier.
+    pub total: u32,
+}
+
+/// The result of guessing a letter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuessOutcome {
+    /// The letter is in the phrase, at these positions (counted in grapheme clusters).
+    Hit { positions: Vec<usize> },
+    /// The letter is not in the phrase, and counts as an incorrect guess.
+    Miss,
+    /// The letter was already guessed, so the guess was ignored.
+    AlreadyGuessed,
+    /// The character cannot be guessed in this game (like whitespace, a letter of another
+    /// alphabet, or punctuation that is already shown), so the guess was ignored.
+    InvalidChar,
+    /// The game is already over, so the guess was ignored.
+    GameAlreadyOver,
+    /// The game had no phrase yet and the word source has no word that can be played with its
+    /// settings, so the guess was ignored.
+    NoPhrase,
+}
+
+impl GuessOutcome {
+    /// Describe the outcome of a guess to the player.
+    ///
+    /// # Arguments
+    ///
+    /// * `guess` - The character that was guessed.
+    ///
+    pub fn message(&self, guess: char) -> String {
+        let guess = normalize_letter(guess);
+        match self {
+            GuessOutcome::Hit { positions } if positions.len() == 1 => {
+                format!("Good guess! {} is in the phrase once.", guess)
+            }
+            GuessOutcome::Hit { positions } => format!(
+                "Good guess! {} is in the phrase {} times.",
+                guess,
+                positions.len()
+            ),
+            GuessOutcome::Miss => format!("Sorry, {} is not in the phrase.", guess),
+            GuessOutcome::AlreadyGuessed => {
+                format!("You already guessed {}. No miss counted.", guess)
+            }
+            GuessOutcome::InvalidChar => format!("{} can't be guessed in this game.", guess),
+            GuessOutcome::GameAlreadyOver => "The game is already over.".to_string(),
+            GuessOutcome::NoPhrase => NO_PHRASE_MESSAGE.to_string(),
+        }
+    }
+}
+
+/// The result of trying to solve the whole phrase at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// The attempt matched the phrase, and the game is won.
+    Solved,
+    /// The attempt didn't match the phrase, and the `SolvePenalty` was applied.
+    Wrong,
+    /// The game is already over, so the attempt was ignored.
+    GameAlreadyOver,
+    /// The game had no phrase yet and the word source has no word that can be played with its
+    /// settings, so the attempt was ignored.
+    NoPhrase,
+}
+
+impl SolveOutcome {
+    /// Describe the outcome of a solve attempt to the player.
+    pub fn message(&self) -> &'static str {
+        match self {
+            SolveOutcome::Solved => "You solved it!",
+            SolveOutcome::Wrong => "Sorry, that's not the phrase.",
+            SolveOutcome::GameAlreadyOver => "The game is already over.",
+            SolveOutcome::NoPhrase => NO_PHRASE_MESSAGE,
+        }
+    }
+}
+
+/// Something a player did in a game that counted, kept in order for the result summary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Move {
+    /// A guessed letter that is in the phrase.
+    Hit,
+    /// A guessed letter that is not in the phrase.
+    Miss,
+    /// A hint revealed a letter.
+    Hint,
+    /// The whole phrase was solved at once.
+    Solved,
+    /// An attempt at solving the whole phrase that didn't match it.
+    WrongSolve,
+}
+
+/// What a wrong attempt at solving the whole phrase costs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SolvePenalty {
+    /// The attempt counts as this many incorrect guesses.
+    Misses(u32),
+    /// The attempt loses the game.
+    EndGame,
+}
+
+impl Default for SolvePenalty {
+    /// A wrong solve costs two misses, so calling the answer is riskier than guessing a letter.
+    fn default() -> Self {
+        SolvePenalty::Misses(2)
+    }
+}
+
+impl SolvePenalty {
+    /// The penalties offered to the player, from mildest to harshest.
+    pub const CHOICES: [SolvePenalty; 4] = [
+        SolvePenalty::Misses(1),
+        SolvePenalty::Misses(2),
+        SolvePenalty::Misses(3),
+        SolvePenalty::EndGame,
+    ];
+
+    /// The penalty, as shown to the player.
+    pub fn name(&self) -> String {
+        match self {
+            SolvePenalty::Misses(1) => "Costs 1 miss".to_string(),
+            SolvePenalty::Misses(misses) => format!("Costs {} misses", misses),
+            SolvePenalty::EndGame => "Ends the game".to_string(),
+        }
+    }
+}
+
+/// Why a phrase entered by a player can't be played.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PhraseError {
+    /// The phrase has nothing to guess, such as an empty phrase or one of only punctuation.
+    NothingToGuess,
+    /// The phrase has characters that can't be guessed in the game's language, in the order they appear.
+    InvalidChars(Vec<char>),
+    /// The game already has a phrase.
+    AlreadyStarted,
+}
+
+impl PhraseError {
+    /// Describe the problem to the player who entered the phrase.
+    pub fn message(&self) -> String {
+        match self {
+            PhraseError::NothingToGuess => "The phrase needs at least one letter.".to_string(),
+            PhraseError::AlreadyStarted => "The game has already started.".to_string(),
+            PhraseError::InvalidChars(chars) => format!(
+                "These characters can't be guessed: {}",
+                chars
+                    .iter()
+                    .map(char::to_string)
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+        }
+    }
+}
+
+/// Where a game is in its life: set up, played, then won or lost.
+///
+/// A game can only move forward. The settings can only be changed while it is set up, and a
+/// finished game stays finished; play again with a new game from `HangmanGameState::next_game`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GameStatus {
+    /// The settings and players are being chosen, and there is no phrase yet.
+    #[default]
+    Setup,
+    /// The phrase is set and letters are being guessed.
+    Playing,
+    /// The phrase was guessed.
+    Won,
+    /// The allowed misses were used up, or a wrong solve ended the game.
+    Lost,
+}
+
+/// The rules a game is played by, chosen while it is set up.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    /// The difficulty level, or `None` if it has not been chosen yet. A game that starts
+    /// without one is played on the default difficulty.
+    pub difficulty: Option<Difficulty>,
+    /// The category of the word source random phrases are picked from, or `None` for any word.
+    pub category: Option<String>,
+    /// The language whose alphabet the game is played with.
+    pub language: Language,
+    /// Flag indicating if guessing a letter also reveals its accented forms that are not in the alphabet.
+    pub fold_accents: bool,
+    /// The kinds of characters besides whitespace that are shown without being guessed.
+    pub auto_reveal: AutoReveal,
+    /// What a wrong attempt at solving the whole phrase costs.
+    pub solve_penalty: SolvePenalty,
+    /// How the incorrect guesses of a hot-seat game count against the allowed misses.
+    pub miss_limit: MissLimit,
+}
+
+impl Default for GameSettings {
+    /// The settings of a game nobody has changed, with accents folded as the default language expects.
+    fn default() -> Self {
+        GameSettings {
+            difficulty: None,
+            category: None,
+            language: Language::default(),
+            fold_accents: Language::default().folds_accents(),
+            auto_reveal: AutoReveal::default(),
+            solve_penalty: SolvePenalty::default(),
+            miss_limit: MissLimit::default(),
+        }
+    }
+}
 
 /// Struct representing the state of the Hangman game.
+///
+/// The fields are private so that a game can only change by the rules: it is set up with a
+/// `HangmanGameBuilder`, started with `start` or `start_with_phrase` (or its first guess), and
+/// played with `guess_letter`, `solve_phrase` and `use_hint` until it is won or lost (see
+/// `GameStatus`).
+///
+/// The state can be saved with serde; the word source is not saved, so a restored game picks
+/// its next phrases from the embedded word list until the front-end sets it again. A restored
+/// game is checked against the rules, so a saved state the rules could not have reached is
+/// corrected or rejected rather than played.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Self", default)]
 pub struct HangmanGameState {
     /// The phrase to guess in the Hangman game.
-    pub phrase_to_guess: String,
+    phrase_to_guess: String,
     // The phrase to guess but with the characters replaced with underscores.
-    pub obfuscated_phrase: String,
-    /// The characters to guess in the Hangman game.
-    pub chars_to_guess: Vec<char>,
+    obfuscated_phrase: String,
+    /// The characters to guess in the Hangman game, one per grapheme cluster of the phrase.
+    chars_to_guess: Vec<char>,
     /// The letters that have been guessed in the Hangman game.
-    pub guessed_letters: Vec<char>,
+    guessed_letters: Vec<char>,
     /// The count of incorrect guesses in the Hangman game.
-    pub incorrect_guess_count: u32,
-    /// The difficulty level of the Hangman game. 4 - 10 Hard, Normal, Easy, Very Easy
-    pub difficulty: u32,
+    incorrect_guess_count: u32,
+    /// The difficulty level of the Hangman game, or `None` if it has not been chosen yet.
+    difficulty: Option<Difficulty>,
+    /// The number of hints used in the Hangman game.
+    hints_used: u32,
     /// Flag indicating if the game is over.
-    pub game_over: bool,
+    game_over: bool,
     /// Flag indicating if the player has won the game.
-    pub win: bool,
-    // Debug Flag
-    pub show_debug: bool,
+    win: bool,
+    /// The source random phrases are picked from.
+    #[serde(skip)]
+    word_source: Arc<dyn WordSource>,
+    /// The category of the word source random phrases are picked from, or `None` for any word.
+    category: Option<String>,
+    /// The language whose alphabet the Hangman game is played with.
+    language: Language,
+    /// Flag indicating if guessing a letter also reveals its accented forms that are not in the alphabet.
+    fold_accents: bool,
+    /// The kinds of characters besides whitespace that are shown without being guessed.
+    auto_reveal: AutoReveal,
+    /// What a wrong attempt at solving the whole phrase costs.
+    solve_penalty: SolvePenalty,
+    /// When the current phrase was set, or `None` if there is no phrase yet.
+    started_at: Option<SystemTime>,
+    /// When the game was won or lost, or `None` if it is not over.
+    finished_at: Option<SystemTime>,
+    /// When the game was paused, or `None` if it is not paused.
+    paused_at: Option<SystemTime>,
+    /// A hint about the phrase written by the player who entered it, or `None` if there is none.
+    clue: Option<String>,
+    /// The players taking turns in a hot-seat game, or empty for a game with a single player.
+    players: Vec<Player>,
+    /// The position in `players` of the player whose turn it is.
+    turn: usize,
+    /// How the incorrect guesses of a hot-seat game count against the allowed misses.
+    miss_limit: MissLimit,
+    /// The seed random phrases and hints are picked with, so the game can be played again.
+    seed: u64,
+    /// Flag indicating if the phrase was entered by a player rather than picked from the word source.
+    entered_phrase: bool,
+    /// The guesses, hints and solve attempts that counted, in order.
+    moves: Vec<Move>,
+    /// The day whose daily puzzle this game is, or `None` for any other game.
+    daily: Option<Day>,
+}
+
+impl Serialize for HangmanGameState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        HangmanGameState::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HangmanGameState {
+    /// Read a saved game, and check it against the rules with `check_restored`. A game that
+    /// can't be right is set up again rather than failing, so it doesn't take the rest of
+    /// whatever it was saved in (such as a GUI profile) down with it.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut game_state = HangmanGameState::deserialize(deserializer)?;
+        let _ = game_state.check_restored();
+        Ok(game_state)
+    }
 }
 
 /// Implement the Default trait for HangmanGameState.
 impl Default for HangmanGameState {
-    /// Create a new instance of HangmanGameState with default values.
+    /// Create a game being set up with the default settings, picking phrases from the embedded word list.
     fn default() -> Self {
-        Self {
+        HangmanGameState::builder().build()
+    }
+}
+
+/// Sets up a new game. Create one with `HangmanGameState::builder`, or with
+/// `HangmanGameState::next_game` to play again with the same settings and players.
+#[derive(Clone)]
+pub struct HangmanGameBuilder {
+    settings: GameSettings,
+    word_source: Arc<dyn WordSource>,
+    players: Vec<String>,
+    seed: Option<u64>,
+    daily: Option<Day>,
+}
+
+impl HangmanGameBuilder {
+    /// Use these settings, replacing any chosen before.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The rules to play by.
+    ///
+    pub fn settings(mut self, settings: GameSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Play on this difficulty.
+    ///
+    /// # Arguments
+    ///
+    /// * `difficulty` - The difficulty level.
+    ///
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.settings.difficulty = Some(difficulty);
+        self
+    }
+
+    /// Pick random phrases from this category of the word source, or from any word.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The category, or `None` for any word.
+    ///
+    pub fn category(mut self, category: Option<String>) -> Self {
+        self.settings.category = category;
+        self
+    }
+
+    /// Play with this language's alphabet, folding accents the way the language expects.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language.
+    ///
+    pub fn language(mut self, language: Language) -> Self {
+        self.settings.language = language;
+        self.settings.fold_accents = language.folds_accents();
+        self
+    }
+
+    /// Pick random phrases from this word source.
+    ///
+    /// # Arguments
+    ///
+    /// * `word_source` - The word source.
+    ///
+    pub fn word_source(mut self, word_source: Arc<dyn WordSource>) -> Self {
+        self.word_source = word_source;
+        self
+    }
+
+    /// Make the game a hot-seat game for these players, the first of them to play first. An
+    /// empty list makes it a single-player game.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names of the players, in the order they take turns.
+    ///
+    pub fn players(mut self, names: impl IntoIterator<Item = String>) -> Self {
+        self.players = names.into_iter().collect();
+        self
+    }
+
+    /// Pick the phrase and hints with this seed, to play a game again. Without one, a random
+    /// seed is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed.
+    ///
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the game up to play a shared puzzle: its settings and seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - The puzzle's code.
+    ///
+    pub fn puzzle(self, puzzle: &PuzzleCode) -> Self {
+        self.settings(puzzle.settings.clone()).seed(puzzle.seed)
+    }
+
+    /// Make the game a day's daily puzzle: a single-player game on the daily settings, in the
+    /// language already chosen, whose seed comes from the day and the word source's words.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - The day of the puzzle.
+    ///
+    pub fn daily(mut self, day: Day) -> Self {
+        self.settings = daily_settings(self.settings.language);
+        self.players.clear();
+        self.daily = Some(day);
+        self
+    }
+
+    /// Create the game, ready to be started. A category the word source doesn't have is dropped.
+    pub fn build(self) -> HangmanGameState {
+        let seed = match self.daily {
+            Some(day) => daily_seed(day, self.word_source.as_ref()),
+            None => self.seed.unwrap_or_else(rand::random),
+        };
+        let GameSettings {
+            difficulty,
+            category,
+            language,
+            fold_accents,
+            auto_reveal,
+            solve_penalty,
+            miss_limit,
+        } = self.settings;
+        let mut game_state = HangmanGameState {
             phrase_to_guess: String::new(),
             obfuscated_phrase: String::new(),
             chars_to_guess: Vec::new(),
             guessed_letters: Vec::new(),
             incorrect_guess_count: 0,
-            difficulty: 0, // 6 is "normal difficulty", 4 is "hard difficulty", 8 is "easy difficulty", and 10 is "very easy difficulty"
+            difficulty,
+            hints_used: 0,
             game_over: false,
             win: false,
-            show_debug: false,
-        }
+            word_source: Arc::new(EmbeddedWordList),
+            category,
+            language,
+            fold_accents,
+            auto_reveal,
+            solve_penalty,
+            started_at: None,
+            finished_at: None,
+            paused_at: None,
+            clue: None,
+            players: self.players.into_iter().map(Player::new).collect(),
+            turn: 0,
+            miss_limit,
+            seed,
+            entered_phrase: false,
+            moves: Vec::new(),
+            daily: self.daily,
+        };
+        game_state.set_word_source(self.word_source);
+        game_state
     }
 }
 
 impl HangmanGameState {
-    /// Create a new instance of HangmanGameState with the given phrase to guess.
+    /// Start setting up a game, with the default settings and the embedded word list.
+    pub fn builder() -> HangmanGameBuilder {
+        HangmanGameBuilder {
+            settings: GameSettings::default(),
+            word_source: Arc::new(EmbeddedWordList),
+            players: Vec::new(),
+            seed: None,
+            daily: None,
+        }
+    }
+
+    /// Create a game on the default settings that is already playing the given phrase.
     ///
     /// # Arguments
     ///
     /// * `phrase_to_guess` - The phrase to guess in the Hangman game.
     ///
-    pub fn new(phrase_to_guess: String) -> Self {
+    /// # Returns
+    ///
+    /// * `Result<HangmanGameState, PhraseError>` - The game, or why the phrase can't be played.
+    pub fn new(phrase_to_guess: String) -> Result<Self, PhraseError> {
+        let mut game_state = HangmanGameState::default();
+        game_state.start_with_phrase(phrase_to_guess, None)?;
+        Ok(game_state)
+    }
+
+    /// Start setting up the next game, with this game's settings, word source and players, and
+    /// a new seed.
+    pub fn next_game(&self) -> HangmanGameBuilder {
+        HangmanGameState::builder()
+            .settings(self.settings())
+            .word_source(self.word_source.clone())
+            .players(self.players.iter().map(|player| player.name.clone()))
+    }
+
+    /// Where the game is in its life.
+    pub fn status(&self) -> GameStatus {
+        match (self.game_over, self.win) {
+            (true, true) => GameStatus::Won,
+            (true, false) => GameStatus::Lost,
+            _ if self.phrase_to_guess.is_empty() => GameStatus::Setup,
+            _ => GameStatus::Playing,
+        }
+    }
+
+    /// Whether the game has been won or lost.
+    pub fn is_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// Whether the game has been won.
+    pub fn is_won(&self) -> bool {
+        self.status() == GameStatus::Won
+    }
+
+    /// The phrase to guess, or an empty string while the game is set up. Front-ends should only
+    /// show it once the game is over.
+    pub fn phrase(&self) -> &str {
+        &self.phrase_to_guess
+    }
+
+    /// The phrase with the characters that are still hidden replaced with underscores.
+    pub fn obfuscated_phrase(&self) -> &str {
+        &self.obfuscated_phrase
+    }
+
+    /// The characters to guess, one per grapheme cluster of the phrase.
+    pub fn chars_to_guess(&self) -> &[char] {
+        &self.chars_to_guess
+    }
+
+    /// The letters that have been guessed, in order.
+    pub fn guessed_letters(&self) -> &[char] {
+        &self.guessed_letters
+    }
+
+    /// The count of incorrect guesses, including the cost of wrong solve attempts.
+    pub fn incorrect_guess_count(&self) -> u32 {
+        self.incorrect_guess_count
+    }
+
+    /// The difficulty level, or `None` if it has not been chosen yet. It is always chosen once
+    /// the game has started.
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        self.difficulty
+    }
+
+    /// The number of hints used.
+    pub fn hints_used(&self) -> u32 {
+        self.hints_used
+    }
+
+    /// The category random phrases are picked from, or `None` for any word.
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// The language whose alphabet the game is played with.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// What a wrong attempt at solving the whole phrase costs.
+    pub fn solve_penalty(&self) -> SolvePenalty {
+        self.solve_penalty
+    }
+
+    /// How the incorrect guesses of a hot-seat game count against the allowed misses.
+    pub fn miss_limit(&self) -> MissLimit {
+        self.miss_limit
+    }
+
+    /// The rules the game is played by.
+    pub fn settings(&self) -> GameSettings {
+        GameSettings {
+            difficulty: self.difficulty,
+            category: self.category.clone(),
+            language: self.language,
+            fold_accents: self.fold_accents,
+            auto_reveal: self.auto_reveal,
+            solve_penalty: self.solve_penalty,
+            miss_limit: self.miss_limit,
+        }
+    }
+
+    /// When the phrase was set, or `None` while the game is set up.
+    pub fn started_at(&self) -> Option<SystemTime> {
+        self.started_at
+    }
+
+    /// When the game was won or lost, or `None` if it is not over.
+    pub fn finished_at(&self) -> Option<SystemTime> {
+        self.finished_at
+    }
+
+    /// The hint about the phrase written by the player who entered it, if any.
+    pub fn clue(&self) -> Option<&str> {
+        self.clue.as_deref()
+    }
+
+    /// The seed random phrases and hints are picked with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The code to share this game's puzzle with, or `None` if the phrase was entered by a
+    /// player, as a code can only reproduce phrases picked from the word source.
+    pub fn puzzle_code(&self) -> Option<PuzzleCode> {
+        (!self.entered_phrase).then(|| PuzzleCode {
+            seed: self.seed,
+            settings: self.settings(),
+        })
+    }
+
+    /// The guesses, hints and solve attempts that counted, in the order they were made.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// The day whose daily puzzle this game is, or `None` if it is not a daily puzzle.
+    pub fn daily(&self) -> Option<Day> {
+        self.daily
+    }
+
+    /// The players taking turns in a hot-seat game, or none in a single-player game.
+    pub fn players(&self) -> &[Player] {
+        &self.players
+    }
+
+    /// The position in `players` of the player whose turn it is.
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// Change the rules of a game that is being set up. A category the word source doesn't
+    /// have is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The rules to play by.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the settings were changed, which they are not once the game has started
+    ///   or for a daily puzzle.
+    pub fn configure(&mut self, settings: GameSettings) -> bool {
+        if self.status() != GameStatus::Setup || self.daily.is_some() {
+            return false;
+        }
+
+        self.difficulty = settings.difficulty;
+        self.category = settings.category;
+        self.language = settings.language;
+        self.fold_accents = settings.fold_accents;
+        self.auto_reveal = settings.auto_reveal;
+        self.solve_penalty = settings.solve_penalty;
+        self.miss_limit = settings.miss_limit;
+        self.set_word_source(self.word_source.clone());
+        true
+    }
+
+    /// Pick the phrase and hints of a game that is being set up with this seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the seed was changed, which it is not once the game has started or
+    ///   for a daily puzzle.
+    pub fn reseed(&mut self, seed: u64) -> bool {
+        if self.status() != GameStatus::Setup || self.daily.is_some() {
+            return false;
+        }
+
+        self.seed = seed;
+        true
+    }
+
+    /// Pick the next random phrases from this word source. The category is kept only if the
+    /// word source has it too. The phrase being played, if any, doesn't change.
+    ///
+    /// # Arguments
+    ///
+    /// * `word_source` - The word source.
+    ///
+    pub fn set_word_source(&mut self, word_source: Arc<dyn WordSource>) {
+        if self
+            .category
+            .as_ref()
+            .is_some_and(|category| !word_source.categories().contains(category))
+        {
+            self.category = None;
+        }
+        self.word_source = word_source;
+    }
+
+    /// Start a game that is being set up with a random phrase from the word source, on the
+    /// default difficulty if none was chosen. Does nothing once the game has started.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), io::Error>` - An error if the word source has no word that can be played with the game's settings. The game is then still being set up.
+    pub fn start(&mut self) -> Result<(), io::Error> {
+        if self.status() == GameStatus::Setup {
+            self.start_if_needed()?;
+        }
+        Ok(())
+    }
+
+    /// Start a game that is being set up with a phrase entered by a player.
+    ///
+    /// The phrase doesn't come from the word source, so the category is cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `phrase` - The phrase to guess, as entered.
+    /// * `clue` - A hint about the phrase for the guessers, or `None` for no hint.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), PhraseError>` - `Ok` if the game started, or why the phrase can't be played.
+    pub fn start_with_phrase(
+        &mut self,
+        phrase: String,
+        clue: Option<String>,
+    ) -> Result<(), PhraseError> {
+        if self.status() != GameStatus::Setup {
+            return Err(PhraseError::AlreadyStarted);
+        }
+        self.check_phrase(&phrase)?;
+
+        self.category = None;
+        self.set_phrase(phrase);
+        self.clue = clue;
+        Ok(())
+    }
+
+    /// A game showing only what a view of it sent to clients does, for drawing it. It has no
+    /// phrase to guess, so it can't be played.
+    ///
+    /// # Arguments
+    ///
+    /// * `view` - The view of the game.
+    ///
+    pub(crate) fn from_view(view: &GameView) -> Self {
         HangmanGameState {
-            phrase_to_guess: phrase_to_guess.clone(),
-            obfuscated_phrase: String::new(),
-            chars_to_guess: phrase_to_guess.chars().collect(),
-            guessed_letters: Vec::new(),
-            incorrect_guess_count: 0,
-            difficulty: 0,
-            game_over: false,
-            win: false,
-            show_debug: false,
+            obfuscated_phrase: view.obfuscated_phrase.clone(),
+            guessed_letters: view.guessed_letters.clone(),
+            incorrect_guess_count: view.incorrect_guess_count,
+            difficulty: view.difficulty,
+            language: view.language,
+            category: view.category.clone(),
+            players: view.players.clone(),
+            turn: view.turn,
+            miss_limit: view.miss_limit,
+            game_over: view.game_over,
+            win: view.win,
+            ..Default::default()
         }
     }
 
-    /// Generate a random word from a file with the specified length.
+    /// Save the game to a JSON file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to save to.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), io::Error>` - An error if the file could not be written.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), io::Error> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+    }
+
+    /// Load a game saved with `save_to_file`.
     ///
     /// # Arguments
     ///
-    /// * `length` - The length of the word to generate.
+    /// * `path` - The path of the file to load.
     ///
     /// # Returns
     ///
-    /// * `Result<String, io::Error>` - The randomly generated word, or an error if no words with the specified length are found.
-    pub fn random_word_from_file(&mut self, length: u32) -> Result<String, io::Error> {
-        let file_contents = include_str!("../worldlist/wordlist.txt");
-        let lines = file_contents.lines();
+    /// * `Result<HangmanGameState, io::Error>` - The loaded game, or an error if the file could not be read, is not a saved game or holds a game that can't be right.
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, io::Error> {
+        let json = fs::read_to_string(path)?;
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let mut game_state = HangmanGameState::deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        game_state
+            .check_restored()
+            .map_err(|reason| io::Error::new(io::ErrorKind::InvalidData, reason))?;
+        Ok(game_state)
+    }
 
-        let mut words = Vec::new();
+    /// Bring a restored game in line with the rules, in case it was saved by an older version
+    /// or edited: the phrase's characters and obfuscation are worked out again, misses and hints
+    /// are kept within the difficulty's limits, and a game that is won or lost by now is over.
+    /// A game whose result can't be right is set up again with the same settings and players.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - The reason the game was set up again, if it was.
+    fn check_restored(&mut self) -> Result<(), String> {
+        if self.phrase_to_guess.is_empty() {
+            let result = if self.game_over {
+                Err("a finished game must have a phrase".to_string())
+            } else {
+                Ok(())
+            };
+            // Nothing can have been played before there is a phrase
+            self.game_over = false;
+            self.clue = None;
+            self.entered_phrase = false;
+            self.guessed_letters.clear();
+            self.moves.clear();
+            self.incorrect_guess_count = 0;
+            self.hints_used = 0;
+            self.started_at = None;
+            self.finished_at = None;
+            self.paused_at = None;
+            self.win = false;
+            self.reset_players();
+            self.obfuscate_phrase();
+            return result;
+        }
 
-        // Iterate over each line in the file
-        for line in lines {
-            // Check if the line has the specified length
-            if line.len() == length as usize {
-                words.push(line.to_string());
-            }
+        self.difficulty.get_or_insert_with(Difficulty::default);
+        self.obfuscate_phrase();
+        let allowed_misses = self.allowed_misses();
+        if !self.counts_misses_per_player() {
+            self.incorrect_guess_count = self.incorrect_guess_count.min(allowed_misses);
+        }
+        for player in &mut self.players {
+            player.misses = player.misses.min(allowed_misses);
+        }
+        let hint_budget = self.profile().map_or(0, |profile| profile.hint_budget);
+        self.hints_used = self.hints_used.min(hint_budget);
+        if self.turn >= self.players.len() {
+            self.turn = 0;
         }
 
-        // Check if no words with the specified length are found
-        if words.is_empty() {
-            return Err(io::Error::new(
-                io::ErrorKind::NotFound,
-                "No words found with the specified length",
-            ));
+        if !self.game_over {
+            self.win = false;
+            self.check_win_or_loss();
+        } else if self.win != self.is_revealed() {
+            self.phrase_to_guess.clear();
+            let _ = self.check_restored();
+            return Err("the game's result doesn't match its guessed letters".to_string());
+        }
+        // Only a game being played can be paused
+        if self.game_over {
+            self.paused_at = None;
         }
+        Ok(())
+    }
+
+    /// Generate a random word from the word source (and category, if one is chosen) that passes the given filter
+    /// and can be played with the game's language and settings (see `check_phrase`).
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The length and frequency band of the word to generate.
+    /// * `rng` - The random number generator to pick with. A generator seeded the same way picks the same word from the same word source.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, io::Error>` - The randomly generated word, or an error if no playable words pass the filter.
+    pub fn random_word_from_file<R: Rng + ?Sized>(
+        &self,
+        filter: &WordFilter,
+        rng: &mut R,
+    ) -> Result<String, io::Error> {
+        let index = match &self.category {
+            Some(category) => self.word_source.category_index(category)?,
+            None => self.word_source.index(),
+        };
 
-        // Generate a random index within the range of the words vector
-        let random_word = words[rand::thread_rng().gen_range(0..words.len())].clone();
+        // Pick a random word from the ones that pass the filter, skipping words that can't be
+        // played, like a line of only punctuation that would start the game already solved
+        let random_word = index
+            .random_word_where(filter, rng, |word| self.check_phrase(word).is_ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "No playable words found matching the specified filter",
+                )
+            })?;
 
-        Ok(random_word)
+        Ok(random_word.to_string())
     }
 
-    pub fn obfuscate_phrase(&mut self) {
+    /// Rebuild `chars_to_guess` and `obfuscated_phrase` from the phrase and the letters guessed so far.
+    ///
+    /// The phrase is walked one grapheme cluster at a time, so a letter with combining marks is
+    /// shown or hidden as a whole. With `fold_accents`, accented letters that are not in the
+    /// alphabet are shown once their base letter is guessed. Characters `auto_reveal` covers
+    /// are shown from the start.
+    fn obfuscate_phrase(&mut self) {
         let mut obfuscated_phrase = String::new();
+        let mut chars_to_guess = Vec::new();
 
-        for c in self.chars_to_guess.iter() {
-            if self.guessed_letters.contains(c) {
-                obfuscated_phrase.push(*c);
-                obfuscated_phrase.push(' ');
-            } else if c.is_whitespace() {
-                obfuscated_phrase.push(*c);
+        for (grapheme, letter) in graphemes_with_letters(&self.phrase_to_guess) {
+            let c = guess_key(letter, self.language, self.fold_accents);
+            chars_to_guess.push(c);
+
+            if self.guessed_letters.contains(&c) || self.auto_reveal.reveals(c) {
+                obfuscated_phrase.push_str(grapheme);
                 obfuscated_phrase.push(' ');
             } else {
                 obfuscated_phrase.push('_');
@@ -115,6 +968,7 @@ impl HangmanGameState {
             }
         }
 
+        self.chars_to_guess = chars_to_guess;
         self.obfuscated_phrase = obfuscated_phrase;
     }
 
@@ -122,94 +976,820 @@ impl HangmanGameState {
     ///
     /// # Arguments
     ///
-    /// * `length` - The length of the phrase to generate.
+    /// * `filter` - The length and frequency band of the phrase to generate.
+    ///
+    /// # Returns
     ///
-    pub fn random_phrase_to_guess(&mut self, length: u32) {
-        // Get a random word from the file with the specified length
+    /// * `Result<(), io::Error>` - An error if the word source has no playable words at all.
+    fn random_phrase_to_guess(&mut self, filter: &WordFilter) -> Result<(), io::Error> {
+        // Get a random word from the word source that passes the filter, falling back to any
+        // word so that small custom word lists stay playable on every difficulty
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
         let phrase = self
-            .random_word_from_file(length)
-            .expect("Error getting random word from file");
+            .random_word_from_file(filter, &mut rng)
+            .or_else(|_| self.random_word_from_file(&WordFilter::any(), &mut rng))?;
 
         // Convert the phrase to uppercase
-        let phrase = phrase.to_uppercase();
-
-        // Update the characters to guess with the characters from the phrase
-        self.chars_to_guess = phrase.chars().collect();
+        let phrase = normalize_phrase(&phrase);
 
         // Update the phrase to guess with the generated phrase
         self.phrase_to_guess = phrase;
-        // Reobfuscate the phrase
+        self.started_at = Some(SystemTime::now());
+        self.finished_at = None;
+        self.clue = None;
+        // Reobfuscate the phrase, which also updates the characters to guess
         self.obfuscate_phrase();
+        Ok(())
     }
 
-    /// Update the guess phrase in the hangman game.
+    /// Play a new phrase from the start, with the default difficulty if none was chosen.
     ///
     /// # Arguments
     ///
     /// * `phrase` - The new phrase to guess.
     ///
-    pub fn update_guess_phrase(&mut self, phrase: String) {
-        let phrase = phrase.to_uppercase();
-        self.chars_to_guess = phrase.chars().collect();
-        self.phrase_to_guess = phrase;
-        self.obfuscate_phrase();
+    fn set_phrase(&mut self, phrase: String) {
+        self.difficulty.get_or_insert_with(Difficulty::default);
+        self.phrase_to_guess = normalize_phrase(&phrase);
+        self.entered_phrase = true;
         self.guessed_letters.clear();
+        self.moves.clear();
+        self.obfuscate_phrase();
         self.incorrect_guess_count = 0;
+        self.hints_used = 0;
+        self.started_at = Some(SystemTime::now());
+        self.finished_at = None;
+        self.paused_at = None;
+        self.clue = None;
+        self.reset_players();
     }
 
-    pub fn check_win_or_loss(&mut self) {
-        let chars_to_guess_without_whitespace: Vec<char> = self
-            .chars_to_guess
+    /// Add a player to a hot-seat game that is being set up, who takes their first turn after
+    /// everyone already playing.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The player's name.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the player was added, which they are not once the game has started.
+    pub fn add_player(&mut self, name: String) -> bool {
+        if self.status() != GameStatus::Setup {
+            return false;
+        }
+
+        self.players.push(Player::new(name));
+        true
+    }
+
+    /// Remove a player from a hot-seat game that is being set up.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The player's position in `players`.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the player was removed, which they are not once the game has started.
+    pub fn remove_player(&mut self, position: usize) -> bool {
+        if self.status() != GameStatus::Setup || position >= self.players.len() {
+            return false;
+        }
+
+        self.drop_player(position);
+        true
+    }
+
+    /// Let a player leave a game, even one being played, like a player who disconnects from an
+    /// online game. If it was their turn, the next player takes it, and with misses per player
+    /// the game is lost once everyone left is out.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The player's position in `players`.
+    ///
+    pub fn player_left(&mut self, position: usize) {
+        if position >= self.players.len() {
+            return;
+        }
+
+        self.drop_player(position);
+        if self.status() == GameStatus::Playing {
+            self.check_win_or_loss();
+        }
+    }
+
+    /// Take a player out of the list of players, keeping the turn with the player it was with
+    /// or passing it on.
+    fn drop_player(&mut self, position: usize) {
+        self.players.remove(position);
+        if position < self.turn {
+            self.turn -= 1;
+        }
+        if self.turn >= self.players.len() {
+            self.turn = 0;
+        }
+    }
+
+    /// The player whose turn it is, or `None` in a single-player game.
+    pub fn current_player(&self) -> Option<&Player> {
+        self.players.get(self.turn)
+    }
+
+    /// Whether a player of a hot-seat game with misses per player has run out of misses and
+    /// sits out the rest of the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - One of the game's players.
+    ///
+    pub fn is_out(&self, player: &Player) -> bool {
+        self.counts_misses_per_player()
+            && self.difficulty.is_some()
+            && player.misses >= self.allowed_misses()
+    }
+
+    /// The players with the highest score, more than one on a tie, or none in a single-player game.
+    pub fn leaders(&self) -> Vec<&Player> {
+        let best = self.players.iter().map(|player| player.score).max();
+        self.players
             .iter()
-            .filter(|c| !c.is_whitespace())
-            .cloned()
-            .collect();
-        println!("{:?}", chars_to_guess_without_whitespace);
-        if chars_to_guess_without_whitespace
+            .filter(|player| Some(player.score) == best)
+            .collect()
+    }
+
+    /// The incorrect guesses that count against the allowed misses: the current player's own
+    /// in a hot-seat game with misses per player, otherwise every incorrect guess.
+    pub fn misses(&self) -> u32 {
+        match self.current_player() {
+            Some(player) if self.counts_misses_per_player() => player.misses,
+            _ => self.incorrect_guess_count,
+        }
+    }
+
+    /// Check that a phrase entered by a player can be played with the game's language and
+    /// settings: every character must either be shown from the start or be guessable, and
+    /// there must be something left to guess.
+    ///
+    /// # Arguments
+    ///
+    /// * `phrase` - The phrase to check, as entered.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), PhraseError>` - `Ok` if the phrase can be played, or why it can't.
+    pub fn check_phrase(&self, phrase: &str) -> Result<(), PhraseError> {
+        let phrase = normalize_phrase(phrase);
+        let mut invalid_chars = Vec::new();
+        let mut to_guess = 0;
+
+        for (_, letter) in graphemes_with_letters(&phrase) {
+            let c = guess_key(letter, self.language, self.fold_accents);
+            if self.auto_reveal.reveals(c) {
+                continue;
+            }
+            if self.is_valid_guess(c) {
+                to_guess += 1;
+            } else if !invalid_chars.contains(&letter) {
+                invalid_chars.push(letter);
+            }
+        }
+
+        if !invalid_chars.is_empty() {
+            Err(PhraseError::InvalidChars(invalid_chars))
+        } else if to_guess == 0 {
+            Err(PhraseError::NothingToGuess)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The rules of the chosen difficulty, or `None` if no difficulty has been chosen yet.
+    pub fn profile(&self) -> Option<DifficultyProfile> {
+        self.difficulty.map(|difficulty| difficulty.profile())
+    }
+
+    /// The number of incorrect guesses allowed before the game is lost.
+    pub fn allowed_misses(&self) -> u32 {
+        self.profile().map_or(0, |profile| profile.allowed_misses)
+    }
+
+    /// The number of incorrect guesses the player can still make.
+    pub fn guesses_left(&self) -> u32 {
+        self.allowed_misses().saturating_sub(self.misses())
+    }
+
+    /// The number of hints the player can still use.
+    pub fn hints_left(&self) -> u32 {
+        self.profile()
+            .map_or(0, |profile| profile.hint_budget)
+            .saturating_sub(self.hints_used)
+    }
+
+    /// The parts of the gallows and the hangman to draw for the incorrect guesses so far.
+    pub fn gallows_parts(&self) -> &'static [GallowsPart] {
+        visible_parts(self.misses(), self.allowed_misses())
+    }
+
+    /// Check whether the game has been won or lost and end it if so.
+    ///
+    /// The game is won once every character that is not shown from the start has been guessed,
+    /// and lost once the allowed misses are used up (by every player, if they each have their own).
+    fn check_win_or_loss(&mut self) {
+        let out_of_misses = if self.counts_misses_per_player() {
+            self.players.iter().all(|player| self.is_out(player))
+        } else {
+            self.allowed_misses() <= self.incorrect_guess_count
+        };
+
+        if self.is_revealed() {
+            self.finish(true);
+        } else if self.difficulty.is_some() && out_of_misses && !self.phrase_to_guess.is_empty() {
+            self.finish(false);
+        }
+    }
+
+    /// Whether every character of the phrase that has to be guessed has been.
+    fn is_revealed(&self) -> bool {
+        self.chars_to_guess
             .iter()
+            .filter(|c| !self.auto_reveal.reveals(**c))
             .all(|c| self.guessed_letters.contains(c))
-        {
-            self.win = true;
-            self.game_over = true;
-        } else if self.difficulty <= self.incorrect_guess_count
-            && self.difficulty != 0
-            && self.phrase_to_guess != ""
-        {
-            self.win = false;
-            self.game_over = true;
+    }
+
+    /// Score the game so far.
+    ///
+    /// Every revealed letter of the phrase (counted once per position) scores points. A win
+    /// adds a bonus for each incorrect guess left and for every second under two minutes the
+    /// game took. The sum is multiplied by the difficulty's `score_multiplier`.
+    pub fn score(&self) -> Score {
+        let revealed_letters = self
+            .chars_to_guess
+            .iter()
+            .filter(|c| !self.auto_reveal.reveals(**c) && self.guessed_letters.contains(c))
+            .count() as u32;
+        let letter_points = revealed_letters * POINTS_PER_LETTER;
+
+        let (misses_bonus, time_bonus) = if self.game_over && self.win {
+            let seconds = self.duration().unwrap_or_default().as_secs();
+            (
+                self.guesses_left() * POINTS_PER_MISS_LEFT,
+                TIME_BONUS_SECONDS.saturating_sub(seconds) as u32,
+            )
+        } else {
+            (0, 0)
+        };
+
+        let multiplier = self
+            .profile()
+            .map_or(100, |profile| profile.score_multiplier);
+        let total = (letter_points + misses_bonus + time_bonus) * multiplier / 100;
+
+        Score {
+            letter_points,
+            misses_bonus,
+            time_bonus,
+            multiplier,
+            total,
+        }
+    }
+
+    /// How long the game has taken: from when the phrase was set until the game ended, or
+    /// until now if it is still going, leaving out the time it was paused. `None` if there is
+    /// no phrase yet.
+    pub fn duration(&self) -> Option<Duration> {
+        let end = (self.finished_at.or(self.paused_at)).unwrap_or_else(SystemTime::now);
+        self.started_at
+            .map(|started_at| end.duration_since(started_at).unwrap_or_default())
+    }
+
+    /// Check whether a character can be guessed in this game.
+    ///
+    /// Letters of the alphabet can be guessed, and so can accented letters whose base letter is
+    /// in the alphabet. Digits, punctuation and symbols can only be guessed when `auto_reveal`
+    /// hides them. Whether the character is in the phrase doesn't matter.
+    ///
+    /// # Arguments
+    ///
+    /// * `guess` - The character to check, as returned by `guess_key`.
+    ///
+    pub fn is_valid_guess(&self, guess: char) -> bool {
+        match char_class(guess) {
+            CharClass::Letter => {
+                self.language
+                    .alphabet()
+                    .contains(&guess_key(guess, self.language, true))
+            }
+            CharClass::Whitespace => false,
+            _ => !guess.is_control() && !self.auto_reveal.reveals(guess),
         }
     }
 
     /// Guess a letter in the hangman game.
     ///
+    /// Only a letter that is valid and hasn't been guessed yet can cost a miss; anything else is
+    /// ignored and reported in the outcome.
+    ///
     /// # Arguments
     ///
     /// * `guess` - The letter to guess.
     ///
-    pub fn guess_letter(&mut self, guess: char) {
-        // Set the default difficulty if it is not already set
-        if self.difficulty == 0 {
-            self.difficulty = 6;
+    /// # Returns
+    ///
+    /// * `GuessOutcome` - Whether the letter was in the phrase, or why the guess was ignored.
+    pub fn guess_letter(&mut self, guess: char) -> GuessOutcome {
+        if self.game_over {
+            return GuessOutcome::GameAlreadyOver;
         }
 
-        // Generate a random phrase if no phrase is set
-        if self.phrase_to_guess == "" {
-            self.random_phrase_to_guess(self.difficulty);
+        // Uppercase (and fold) the guess the same way the phrase was
+        let guess = guess_key(normalize_letter(guess), self.language, self.fold_accents);
+        if !self.is_valid_guess(guess) {
+            return GuessOutcome::InvalidChar;
+        }
+        if self.guessed_letters.contains(&guess) {
+            return GuessOutcome::AlreadyGuessed;
+        }
+
+        if self.start_if_needed().is_err() {
+            return GuessOutcome::NoPhrase;
         }
 
         // Add the guessed letter to the list of guessed letters
         self.guessed_letters.push(guess);
         self.obfuscate_phrase();
-        // Check if the guessed letter is in the phrase
-        if self.chars_to_guess.contains(&guess) {
-            // Check if all the characters in the phrase have been guessed
-            self.check_win_or_loss();
-        } else {
-            // Increment the incorrect guess count
+
+        let positions: Vec<usize> = self
+            .chars_to_guess
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == guess)
+            .map(|(position, _)| position)
+            .collect();
+        let outcome = if positions.is_empty() {
             self.incorrect_guess_count += 1;
+            self.moves.push(Move::Miss);
+            self.end_turn(0, 1);
+            GuessOutcome::Miss
+        } else {
+            self.moves.push(Move::Hit);
+            self.end_turn(positions.len(), 0);
+            GuessOutcome::Hit { positions }
+        };
+
+        // Check if the phrase has been guessed or the maximum incorrect guess count has been reached
+        self.check_win_or_loss();
+        outcome
+    }
+
+    /// Try to solve the whole phrase at once.
+    ///
+    /// The attempt is normalized and folded like single guesses, and characters that are shown
+    /// from the start (whitespace, and punctuation unless it is hidden) don't have to be typed,
+    /// so `wall e` solves `WALL-E`. A wrong attempt costs the `solve_penalty`.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The phrase the player thinks it is.
+    ///
+    /// # Returns
+    ///
+    /// * `SolveOutcome` - Whether the attempt solved the phrase.
+    pub fn solve_phrase(&mut self, attempt: &str) -> SolveOutcome {
+        if self.game_over {
+            return SolveOutcome::GameAlreadyOver;
+        }
+
+        if self.start_if_needed().is_err() {
+            return SolveOutcome::NoPhrase;
+        }
+
+        let attempt = normalize_phrase(attempt);
+        let attempt_keys: Vec<char> = graphemes_with_letters(&attempt)
+            .into_iter()
+            .map(|(_, letter)| guess_key(letter, self.language, self.fold_accents))
+            .filter(|c| !self.auto_reveal.reveals(*c))
+            .collect();
+        let phrase_keys: Vec<char> = self
+            .chars_to_guess
+            .iter()
+            .copied()
+            .filter(|c| !self.auto_reveal.reveals(*c))
+            .collect();
+
+        if attempt_keys == phrase_keys {
+            let revealed = phrase_keys
+                .iter()
+                .filter(|c| !self.guessed_letters.contains(c))
+                .count();
+            self.moves.push(Move::Solved);
+            self.end_turn(revealed, 0);
+
+            // Reveal the rest of the phrase
+            for c in phrase_keys {
+                if !self.guessed_letters.contains(&c) {
+                    self.guessed_letters.push(c);
+                }
+            }
+            self.obfuscate_phrase();
+            self.check_win_or_loss();
+            return SolveOutcome::Solved;
+        }
+
+        self.moves.push(Move::WrongSolve);
+        match self.solve_penalty {
+            SolvePenalty::Misses(misses) => {
+                self.incorrect_guess_count += misses;
+                self.end_turn(0, misses);
+                self.check_win_or_loss();
+            }
+            // With misses per player, only the player who tried is out of the game
+            SolvePenalty::EndGame if self.counts_misses_per_player() => {
+                let misses = self.guesses_left();
+                self.incorrect_guess_count += misses;
+                self.end_turn(0, misses);
+                self.check_win_or_loss();
+            }
+            SolvePenalty::EndGame => self.finish(false),
+        }
+        SolveOutcome::Wrong
+    }
+
+    /// Reveal a random letter that has not been guessed yet, without counting it as a miss.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<char>` - The revealed letter, or `None` if the hint budget is spent, the game is over or no phrase can be picked.
+    pub fn use_hint(&mut self) -> Option<char> {
+        // The game only starts for a hint that is given, on the difficulty it will start with
+        let hint_budget = self.difficulty.unwrap_or_default().profile().hint_budget;
+        if self.game_over || self.hints_used >= hint_budget {
+            return None;
+        }
+
+        self.start_if_needed().ok()?;
+
+        let hidden_letters: Vec<char> = self
+            .chars_to_guess
+            .iter()
+            .filter(|c| !self.auto_reveal.reveals(**c) && !self.guessed_letters.contains(c))
+            .cloned()
+            .collect();
+        // Each hint has a seed of its own, so a game played again reveals the same letters
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed.wrapping_add(self.hints_used.into()));
+        if hidden_letters.is_empty() {
+            return None;
+        }
+        // Drawn as a u64, like the phrase, so the seed reveals the same letter on every target
+        let letter = hidden_letters[rng.gen_range(0..hidden_letters.len() as u64) as usize];
+
+        self.hints_used += 1;
+        self.guessed_letters.push(letter);
+        self.moves.push(Move::Hint);
+        self.obfuscate_phrase();
+        self.check_win_or_loss();
+
+        Some(letter)
+    }
+
+    /// Whether the players of a hot-seat game each have their own misses.
+    fn counts_misses_per_player(&self) -> bool {
+        !self.players.is_empty() && self.miss_limit == MissLimit::PerPlayer
+    }
+
+    /// Credit the player whose turn it is and pass the turn to the next player still in the game.
+    /// Does nothing in a single-player game.
+    ///
+    /// # Arguments
+    ///
+    /// * `revealed` - The number of letters of the phrase the player revealed.
+    /// * `misses` - The incorrect guesses the player's turn cost.
+    ///
+    fn end_turn(&mut self, revealed: usize, misses: u32) {
+        let count = self.players.len();
+        if count == 0 {
+            return;
+        }
+
+        let turn = self.turn.min(count - 1);
+        let player = &mut self.players[turn];
+        player.score += revealed as u32 * POINTS_PER_LETTER;
+        player.misses += misses;
+
+        // Skip players who are out; if everyone is, the game is lost and the turn stays
+        self.turn = (1..=count)
+            .map(|step| (turn + step) % count)
+            .find(|&next| !self.is_out(&self.players[next]))
+            .unwrap_or(turn);
+    }
+
+    /// Clear the players' scores and misses for a new phrase, the first player to play first.
+    fn reset_players(&mut self) {
+        for player in &mut self.players {
+            player.score = 0;
+            player.misses = 0;
+        }
+        self.turn = 0;
+    }
 
-            // Check if the maximum incorrect guess count has been reached
-            self.check_win_or_loss()
+    /// End the game, remembering when it ended the first time.
+    fn finish(&mut self, win: bool) {
+        self.win = win;
+        self.game_over = true;
+        self.finished_at.get_or_insert_with(SystemTime::now);
+    }
+
+    /// Stop the clock of a game being played, so the time until it is resumed doesn't count
+    /// towards its duration and score. Does nothing if the game is not being played.
+    pub fn pause(&mut self) {
+        if self.status() == GameStatus::Playing && self.paused_at.is_none() {
+            self.paused_at = Some(SystemTime::now());
+        }
+    }
+
+    /// Start the clock of a paused game again. Guessing, solving or taking a hint also resumes
+    /// the game.
+    pub fn resume(&mut self) {
+        let Some(paused_at) = self.paused_at.take() else {
+            return;
+        };
+        let paused_for = SystemTime::now()
+            .duration_since(paused_at)
+            .unwrap_or_default();
+        self.started_at = self.started_at.map(|started_at| started_at + paused_for);
+    }
+
+    /// Whether the game's clock is stopped.
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Set the default difficulty and generate a random phrase if they have not been set yet.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), io::Error>` - An error if no phrase could be picked, leaving the game being set up.
+    fn start_if_needed(&mut self) -> Result<(), io::Error> {
+        self.resume();
+
+        // Set the default difficulty if it is not already set
+        let difficulty = *self.difficulty.get_or_insert_with(Difficulty::default);
+
+        // Generate a random phrase if no phrase is set
+        if self.phrase_to_guess.is_empty() {
+            let filter = difficulty.profile().words.for_language(self.language);
+            self.random_phrase_to_guess(&filter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordsource::WordList;
+
+    fn words(words: &[&str]) -> Arc<dyn WordSource> {
+        let words = words.iter().map(|word| word.to_string()).collect();
+        Arc::new(WordList::new("test", words).unwrap())
+    }
+
+    /// A game started with a phrase, on Normal unless the settings say otherwise.
+    fn game(phrase: &str, settings: GameSettings, players: &[&str]) -> HangmanGameState {
+        let mut game_state = HangmanGameState::builder()
+            .settings(settings)
+            .players(players.iter().map(|name| name.to_string()))
+            .build();
+        game_state
+            .start_with_phrase(phrase.to_string(), None)
+            .unwrap();
+        game_state
+    }
+
+    fn hot_seat(miss_limit: MissLimit, solve_penalty: SolvePenalty) -> GameSettings {
+        GameSettings {
+            miss_limit,
+            solve_penalty,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn guesses_report_their_outcome() {
+        let mut game_state = HangmanGameState::new("CACTUS".to_string()).unwrap();
+        assert_eq!(
+            game_state.guess_letter('c'),
+            GuessOutcome::Hit {
+                positions: vec![0, 2]
+            }
+        );
+        assert_eq!(game_state.guess_letter('C'), GuessOutcome::AlreadyGuessed);
+        assert_eq!(game_state.guess_letter('Z'), GuessOutcome::Miss);
+        assert_eq!(game_state.guess_letter('Ж'), GuessOutcome::InvalidChar);
+        assert_eq!(game_state.guess_letter(' '), GuessOutcome::InvalidChar);
+        // Only the miss counted
+        assert_eq!(game_state.incorrect_guess_count(), 1);
+        assert_eq!(game_state.moves(), [Move::Hit, Move::Miss]);
+
+        for letter in "ATUS".chars() {
+            game_state.guess_letter(letter);
+        }
+        assert_eq!(game_state.status(), GameStatus::Won);
+        assert_eq!(game_state.guess_letter('Q'), GuessOutcome::GameAlreadyOver);
+    }
+
+    #[test]
+    fn solving_ignores_case_and_characters_shown_from_the_start() {
+        let mut game_state = HangmanGameState::new("WALL-E".to_string()).unwrap();
+        assert_eq!(game_state.solve_phrase("wall e"), SolveOutcome::Solved);
+        assert_eq!(game_state.status(), GameStatus::Won);
+        assert_eq!(
+            game_state.solve_phrase("WALL-E"),
+            SolveOutcome::GameAlreadyOver
+        );
+    }
+
+    #[test]
+    fn wrong_solves_cost_the_penalty() {
+        let mut game_state = game("CAT", GameSettings::default(), &[]);
+        assert_eq!(game_state.solve_phrase("DOG"), SolveOutcome::Wrong);
+        assert_eq!(game_state.incorrect_guess_count(), 2);
+        assert_eq!(game_state.status(), GameStatus::Playing);
+
+        let settings = GameSettings {
+            solve_penalty: SolvePenalty::EndGame,
+            ..Default::default()
+        };
+        let mut game_state = game("CAT", settings, &[]);
+        assert_eq!(game_state.solve_phrase("DOG"), SolveOutcome::Wrong);
+        assert_eq!(game_state.status(), GameStatus::Lost);
+    }
+
+    #[test]
+    fn hot_seat_turns_rotate_and_share_misses() {
+        let settings = hot_seat(MissLimit::Shared, SolvePenalty::default());
+        let mut game_state = game("CAT", settings, &["Ann", "Bob", "Cy"]);
+        game_state.guess_letter('Z');
+        game_state.guess_letter('C');
+        assert_eq!(game_state.current_player().unwrap().name, "Cy");
+        game_state.guess_letter('Q');
+        assert_eq!(game_state.current_player().unwrap().name, "Ann");
+        assert_eq!(game_state.misses(), 2);
+        assert_eq!(game_state.players()[1].score, POINTS_PER_LETTER);
+    }
+
+    #[test]
+    fn hot_seat_players_can_have_their_own_misses() {
+        let settings = hot_seat(MissLimit::PerPlayer, SolvePenalty::default());
+        let mut game_state = game("CAT", settings, &["Ann", "Bob"]);
+        game_state.guess_letter('Z');
+        assert_eq!(game_state.players()[0].misses, 1);
+        // It's Bob's turn, who hasn't missed yet
+        assert_eq!(game_state.misses(), 0);
+        assert_eq!(game_state.incorrect_guess_count(), 1);
+    }
+
+    #[test]
+    fn wrong_solve_that_ends_the_game_knocks_out_only_that_player() {
+        let settings = hot_seat(MissLimit::PerPlayer, SolvePenalty::EndGame);
+        let mut game_state = game("CAT", settings, &["Ann", "Bob"]);
+        assert_eq!(game_state.solve_phrase("DOG"), SolveOutcome::Wrong);
+        assert!(game_state.is_out(&game_state.players()[0]));
+        assert_eq!(game_state.status(), GameStatus::Playing);
+        assert_eq!(game_state.current_player().unwrap().name, "Bob");
+        // Ann is skipped from now on, so Bob keeps the turn
+        game_state.guess_letter('Z');
+        assert_eq!(game_state.current_player().unwrap().name, "Bob");
+        game_state.solve_phrase("DOG");
+        assert_eq!(game_state.status(), GameStatus::Lost);
+    }
+
+    #[test]
+    fn games_move_through_the_statuses_in_order() {
+        let mut game_state = HangmanGameState::builder().build();
+        assert_eq!(game_state.status(), GameStatus::Setup);
+        let settings = GameSettings {
+            difficulty: Some(Difficulty::Hard),
+            ..Default::default()
+        };
+        assert!(game_state.configure(settings.clone()));
+        assert!(game_state.add_player("Ann".to_string()));
+        assert!(game_state.reseed(7));
+
+        game_state
+            .start_with_phrase("CAT".to_string(), None)
+            .unwrap();
+        assert_eq!(game_state.status(), GameStatus::Playing);
+        assert!(!game_state.configure(GameSettings::default()));
+        assert!(!game_state.add_player("Bob".to_string()));
+        assert!(!game_state.reseed(8));
+        assert_eq!(
+            game_state.start_with_phrase("DOG".to_string(), None),
+            Err(PhraseError::AlreadyStarted)
+        );
+
+        game_state.solve_phrase("CAT");
+        assert_eq!(game_state.status(), GameStatus::Won);
+        assert_eq!(game_state.use_hint(), None);
+
+        let next = game_state.next_game().build();
+        assert_eq!(next.status(), GameStatus::Setup);
+        assert_eq!(next.settings(), settings);
+        assert_eq!(next.players()[0].name, "Ann");
+    }
+
+    #[test]
+    fn games_survive_a_serde_round_trip() {
+        let settings = hot_seat(MissLimit::Shared, SolvePenalty::default());
+        let mut game_state = game("CACTUS", settings, &["Ann", "Bob"]);
+        game_state.guess_letter('C');
+        game_state.guess_letter('Z');
+
+        let json = serde_json::to_string(&game_state).unwrap();
+        let restored: HangmanGameState = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.status(), GameStatus::Playing);
+        assert_eq!(restored.phrase(), "CACTUS");
+        assert_eq!(restored.obfuscated_phrase(), game_state.obfuscated_phrase());
+        assert_eq!(restored.guessed_letters(), ['C', 'Z']);
+        assert_eq!(restored.incorrect_guess_count(), 1);
+        assert_eq!(restored.moves(), game_state.moves());
+        assert_eq!(restored.players(), game_state.players());
+        assert_eq!(restored.turn(), 0);
+        assert_eq!(restored.seed(), game_state.seed());
+    }
+
+    /// Restore a game from its JSON after changing it.
+    fn restore_edited(
+        game_state: &HangmanGameState,
+        edit: impl FnOnce(&mut serde_json::Value),
+    ) -> HangmanGameState {
+        let mut json = serde_json::to_value(game_state).unwrap();
+        edit(&mut json);
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn restored_games_are_brought_in_line_with_the_rules() {
+        let game_state = game("CAT", GameSettings::default(), &[]);
+
+        let restored = restore_edited(&game_state, |json| {
+            json["incorrect_guess_count"] = 99.into();
+        });
+        assert_eq!(restored.incorrect_guess_count(), 6);
+        assert_eq!(restored.status(), GameStatus::Lost);
+
+        let restored = restore_edited(&game_state, |json| {
+            json["guessed_letters"] = serde_json::json!(['C', 'A', 'T']);
+        });
+        assert_eq!(restored.status(), GameStatus::Won);
+
+        // A result that can't be right sets the game up again with its settings and players
+        let restored = restore_edited(&game_state, |json| {
+            json["game_over"] = true.into();
+            json["win"] = true.into();
+        });
+        assert_eq!(restored.status(), GameStatus::Setup);
+        assert_eq!(restored.phrase(), "");
+        assert_eq!(restored.settings(), game_state.settings());
+    }
+
+    #[test]
+    fn loading_a_game_that_cant_be_right_is_an_error() {
+        let mut json = serde_json::to_value(game("CAT", GameSettings::default(), &[])).unwrap();
+        json["game_over"] = true.into();
+        json["win"] = true.into();
+        let path =
+            std::env::temp_dir().join(format!("rusty-hangman-corrupt-{}.json", std::process::id()));
+        fs::write(&path, json.to_string()).unwrap();
+
+        let error = HangmanGameState::load_from_file(&path).err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn unplayable_words_are_never_picked() {
+        let word_source = words(&["!!!", "CAT", "ЖУК", "???"]);
+        for seed in 0..20 {
+            let mut game_state = HangmanGameState::builder()
+                .word_source(word_source.clone())
+                .seed(seed)
+                .build();
+            game_state.start().unwrap();
+            assert_eq!(game_state.phrase(), "CAT");
         }
     }
+
+    #[test]
+    fn no_playable_words_leaves_the_game_in_setup() {
+        let mut game_state = HangmanGameState::builder()
+            .word_source(words(&["!!!", "ЖУК"]))
+            .build();
+        assert!(game_state.start().is_err());
+        assert_eq!(game_state.guess_letter('A'), GuessOutcome::NoPhrase);
+        assert_eq!(game_state.solve_phrase("CAT"), SolveOutcome::NoPhrase);
+        assert_eq!(game_state.use_hint(), None);
+        assert_eq!(game_state.status(), GameStatus::Setup);
+    }
 }
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alphabet::Language;
+use crate::difficulty::Difficulty;
+use crate::hangmangame::HangmanGameState;
+
+/// A finished game, as kept in the history.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// The phrase that was played.
+    pub phrase: String,
+    /// The difficulty the game was played on.
+    pub difficulty: Difficulty,
+    /// The language the game was played in.
+    pub language: Language,
+    /// The category the phrase came from, if any.
+    pub category: Option<String>,
+    /// Every letter guessed or revealed by a hint, in order.
+    pub guesses: Vec<char>,
+    /// The guessed letters that were not in the phrase, in order.
+    pub missed_letters: Vec<char>,
+    /// The number of incorrect guesses, including the cost of wrong solve attempts.
+    pub incorrect_guess_count: u32,
+    /// The number of hints used.
+    pub hints_used: u32,
+    /// How long the game took.
+    pub duration: Duration,
+    /// Flag indicating if the game was won.
+    pub win: bool,
+    /// When the game ended.
+    pub finished_at: SystemTime,
+}
+
+/// Every finished game, oldest first. Front-ends keep it with the rest of their saved state.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    /// The recorded games, oldest first.
+    pub records: Vec<GameRecord>,
+}
+
+/// Statistics over the games in a `History`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    /// The number of games played.
+    pub games: usize,
+    /// The number of games won.
+    pub wins: usize,
+    /// The number of games won in a row, up to the last game.
+    pub current_streak: usize,
+    /// The most games won in a row.
+    pub best_streak: usize,
+    /// The average number of incorrect guesses per game for each difficulty that has been played, from easiest to hardest.
+    pub average_misses: Vec<(Difficulty, f64)>,
+    /// Letters that were guessed and not in the phrase, with how often, most missed first.
+    pub most_missed_letters: Vec<(char, usize)>,
+}
+
+impl GameRecord {
+    /// Make a record of a finished game.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game to record.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<GameRecord>` - The record, or `None` if the game is not over.
+    pub fn from_game(game_state: &HangmanGameState) -> Option<Self> {
+        if !game_state.is_over() {
+            return None;
+        }
+
+        Some(GameRecord {
+            phrase: game_state.phrase().to_string(),
+            difficulty: game_state.difficulty().unwrap_or_default(),
+            language: game_state.language(),
+            category: game_state.category().map(str::to_string),
+            guesses: game_state.guessed_letters().to_vec(),
+            missed_letters: game_state
+                .guessed_letters()
+                .iter()
+                .filter(|letter| !game_state.chars_to_guess().contains(letter))
+                .copied()
+                .collect(),
+            incorrect_guess_count: game_state.incorrect_guess_count(),
+            hints_used: game_state.hints_used(),
+            duration: game_state.duration().unwrap_or_default(),
+            win: game_state.is_won(),
+            finished_at: game_state.finished_at().unwrap_or_else(SystemTime::now),
+        })
+    }
+}
+
+impl History {
+    /// Add a finished game to the history.
+    ///
+    /// A game that is not over, or that was already recorded, is not added.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game to record.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the game was added.
+    pub fn record(&mut self, game_state: &HangmanGameState) -> bool {
+        let Some(record) = GameRecord::from_game(game_state) else {
+            return false;
+        };
+
+        // A finished game that was saved and loaded again ends at the same moment
+        if self.records.iter().any(|existing| {
+            existing.finished_at == record.finished_at && existing.phrase == record.phrase
+        }) {
+            return false;
+        }
+
+        self.records.push(record);
+        true
+    }
+
+    /// Work out the statistics over every recorded game.
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            games: self.records.len(),
+            ..Default::default()
+        };
+
+        let mut streak = 0;
+        for record in &self.records {
+            if record.win {
+                stats.wins += 1;
+                streak += 1;
+                stats.best_streak = stats.best_streak.max(streak);
+            } else {
+                streak = 0;
+            }
+        }
+        stats.current_streak = streak;
+
+        for difficulty in Difficulty::ALL {
+            let misses: Vec<u32> = self
+                .records
+                .iter()
+                .filter(|record| record.difficulty == difficulty)
+                .map(|record| record.incorrect_guess_count)
+                .collect();
+            if !misses.is_empty() {
+                let average = misses.iter().sum::<u32>() as f64 / misses.len() as f64;
+                stats.average_misses.push((difficulty, average));
+            }
+        }
+
+        let mut missed: HashMap<char, usize> = HashMap::new();
+        for letter in self
+            .records
+            .iter()
+            .flat_map(|record| &record.missed_letters)
+        {
+            *missed.entry(*letter).or_default() += 1;
+        }
+        stats.most_missed_letters = missed.into_iter().collect();
+        // Most missed first, then alphabetically so ties keep a stable order
+        stats
+            .most_missed_letters
+            .sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        stats
+    }
+}
+
+impl Stats {
+    /// The share of games won, from 0.0 to 1.0, or 0.0 if no games have been played.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64
+        }
+    }
+}
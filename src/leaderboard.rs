@@ -0,0 +1,71 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::difficulty::Difficulty;
+use crate::hangmangame::HangmanGameState;
+
+/// A scored game on a `Leaderboard`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// The game's score (see `HangmanGameState::score`).
+    pub score: u32,
+    /// The phrase that was played.
+    pub phrase: String,
+    /// The difficulty the game was played on.
+    pub difficulty: Difficulty,
+    /// Flag indicating if the game was won.
+    pub win: bool,
+    /// When the game ended.
+    pub finished_at: SystemTime,
+}
+
+/// The highest-scoring finished games, best first.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    /// The entries, best first, at most `Leaderboard::SIZE` of them.
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    /// The number of entries a leaderboard keeps.
+    pub const SIZE: usize = 10;
+
+    /// Add a finished game to the leaderboard if it scored high enough.
+    ///
+    /// Games that are not over, scored nothing or were played by several players in turn are
+    /// not added. Ties keep the earlier game ahead.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The finished game.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The game's position on the leaderboard (0 for the top), or `None` if it didn't make it.
+    pub fn submit(&mut self, game_state: &HangmanGameState) -> Option<usize> {
+        let score = game_state.score().total;
+        if !game_state.is_over() || score == 0 || !game_state.players().is_empty() {
+            return None;
+        }
+
+        let position = self.entries.partition_point(|entry| entry.score >= score);
+        if position >= Self::SIZE {
+            return None;
+        }
+
+        self.entries.insert(
+            position,
+            LeaderboardEntry {
+                score,
+                phrase: game_state.phrase().to_string(),
+                difficulty: game_state.difficulty().unwrap_or_default(),
+                win: game_state.is_won(),
+                finished_at: game_state.finished_at().unwrap_or_else(SystemTime::now),
+            },
+        );
+        self.entries.truncate(Self::SIZE);
+
+        Some(position)
+    }
+}
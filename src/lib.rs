@@ -0,0 +1,40 @@
+//! The Rusty Hangman game engine.
+//!
+//! This crate holds the rules of the game (state, guessing, win/loss and word selection)
+//! without any dependency on a front-end, so the same engine can drive the eframe GUI,
+//! bots, servers and tests.
+
+pub mod alphabet;
+pub mod cli;
+pub mod daily;
+pub mod difficulty;
+pub mod gallows;
+pub mod hangmangame;
+pub mod history;
+pub mod leaderboard;
+pub mod net;
+pub mod players;
+pub mod puzzle;
+pub mod theme;
+pub mod wordindex;
+pub mod wordsource;
+
+pub use alphabet::{AutoReveal, CharClass, Language, ALPHABET};
+pub use daily::{daily_seed, daily_settings, daily_summary, DailyAttempt, Day, DAILY_DIFFICULTY};
+pub use difficulty::{Difficulty, DifficultyProfile, WordFilter};
+pub use gallows::{visible_parts, GallowsPart};
+pub use hangmangame::{
+    GameSettings, GameStatus, GuessOutcome, HangmanGameBuilder, HangmanGameState, Move,
+    PhraseError, Score, SolveOutcome, SolvePenalty,
+};
+pub use history::{GameRecord, History, Stats};
+pub use leaderboard::{Leaderboard, LeaderboardEntry};
+pub use net::{ClientMessage, Connection, GameView, ServerMessage};
+pub use players::{MissLimit, Player};
+pub use puzzle::PuzzleCode;
+pub use theme::{HangmanRenderer, Shape, StageScale, Theme};
+pub use wordindex::WordIndex;
+pub use wordsource::{
+    category_display_name, embedded_word_source, open_word_source, CategoryDirectory,
+    EmbeddedWordList, LanguagePack, WordFile, WordList, WordSource,
+};
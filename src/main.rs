@@ -1,15 +1,36 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
 // use std::{char, io::stdin};
-mod hangmangame;
-use std::default;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 use eframe::egui::{self};
-use hangmangame::HangmanGameState;
+use rusty_hangman::cli::{CliOptions, USAGE};
+use rusty_hangman::net::DEFAULT_ADDRESS;
+use rusty_hangman::theme::{Point, Rgb};
+use rusty_hangman::{
+    category_display_name, daily_summary, embedded_word_source, open_word_source, ClientMessage,
+    Connection, DailyAttempt, Day, Difficulty, GameSettings, GameStatus, GameView,
+    HangmanGameState, HangmanRenderer, History, Language, Leaderboard, MissLimit, PuzzleCode,
+    ServerMessage, Shape, SolvePenalty, Theme, WordSource, DAILY_DIFFICULTY,
+};
+use serde::{Deserialize, Serialize};
 
 fn main() {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    let cli_options = match CliOptions::from_env() {
+        Ok(cli_options) => cli_options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli_options.help {
+        println!("{}", USAGE);
+        return;
+    }
+
     // Define the options for the native application
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -20,60 +41,1414 @@ fn main() {
                     .expect("Failed to load icon"),
             ),
 
-        ..Default::default()
-    };
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "Rusty Hangman Game!",
+        options,
+        Box::new(|cc| Box::new(HangmanApp::new(cc, cli_options))),
+    )
+    .expect("Failed to run native application! Panic!");
+}
+
+/// The storage key of the list of player profiles.
+const PROFILES_KEY: &str = "profiles";
+
+/// The profile the state saved before there were profiles is opened as.
+const LEGACY_PROFILE: &str = "Player";
+
+/// The storage key a profile's state is saved under.
+fn profile_key(name: &str) -> String {
+    format!("profile/{}", name)
+}
+
+/// The player profiles, saved by eframe next to the state of each profile.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct Profiles {
+    names: Vec<String>,   // Every profile, in the order they were created
+    last: Option<String>, // The profile that was open when the app was last closed
+}
+
+/// The screen the app shows, one per phase of a game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum GamePhase {
+    /// Continue or start a game, play online, save or load the game, or switch profiles.
+    #[default]
+    MainMenu,
+    /// Choose the settings and players of a new game, or enter a phrase for another player.
+    Setup,
+    /// Guess the phrase.
+    Playing,
+    /// See how the game ended, the score and the high scores.
+    RoundOver,
+    /// The game waits, with the phrase and the hangman hidden.
+    Paused,
+}
+
+/// A game joined on a multiplayer server, which owns the game and sends its state.
+struct OnlineGame {
+    connection: Connection,      // The connection to the server
+    address: String,             // The address of the server
+    spectator: bool,             // Flag indicating whether the room is only being watched
+    view: Option<GameView>,      // The state of the room's game the server sent last
+    message: String,             // What the server said last: a change in the game, or an error
+    chat: Vec<(String, String)>, // Everything said in the room since joining: (who, what)
+    chat_text: String,           // The chat line entered by the user
+    guess_text: String,          // The letter entered by the user as a guess
+    solve_text: String, // The whole phrase entered by the user as an attempt to solve the game
+}
+
+/// Represents a Hangman application.
+///
+/// Each player profile has its own game, settings, history and leaderboard, saved by eframe
+/// when the app closes or the profile is switched, and restored when the profile is opened.
+/// Word sources and themes are saved as the paths they were loaded from, and loaded again.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct HangmanApp {
+    game_state: HangmanGameState, // The current state of the Hangman game
+    #[serde(skip)]
+    settings: GameSettings, // The settings chosen on the setup screen, restored from the game
+    #[serde(skip)]
+    phase: GamePhase, // The screen being shown
+    #[serde(skip)]
+    show_confirmation_dialog: bool, // Flag indicating whether the exit confirmation dialog should be shown
+    #[serde(skip)]
+    allowed_to_close: bool, // Flag indicating whether the application is allowed to close
+    #[serde(skip)]
+    input_text: String, // The text entered by the user as input for guessing
+    #[serde(skip)]
+    submitted_text: String, // The text submitted by the user/program as a guess
+    #[serde(skip)]
+    input_phrase: String, // The phrase entered by the setter as input for the game
+    #[serde(skip)]
+    show_input_phrase: bool, // Flag indicating whether the setter's phrase is shown instead of masked
+    #[serde(skip)]
+    input_clue: String, // The optional hint entered by the setter for the guesser
+    #[serde(skip)]
+    phrase_error: Option<String>, // Why the setter's phrase can't be played
+    #[serde(skip)]
+    new_player_name: String, // The name entered by the user for a new hot-seat player
+    #[serde(skip)]
+    handing_off: bool, // Flag indicating whether the setter's phrase is ready and the guesser is taking over
+    ui_debug: bool, // Flag indicating whether debug information should be shown
+    #[serde(skip)]
+    word_source: Arc<dyn WordSource>, // The source new games pick their random phrase from
+    word_source_path: String, // The word file or directory path entered by the user
+    #[serde(skip)]
+    word_source_error: Option<String>, // The error from the last attempt to load a word source
+    custom_word_source: bool, // Flag indicating whether the word source was loaded by the user rather than embedded
+    #[serde(skip)]
+    guess_message: String, // What happened to the last guess or hint, shown to the user
+    #[serde(skip)]
+    solve_text: String, // The whole phrase entered by the user as an attempt to solve the game
+    #[serde(skip)]
+    themes: Vec<Theme>, // The built-in themes and any loaded by the user
+    theme: usize,             // The position in `themes` of the theme the hangman is drawn with
+    theme_path: String,       // The theme file path entered by the user
+    theme_files: Vec<String>, // The theme files loaded by the user, loaded again on startup
+    #[serde(skip)]
+    theme_error: Option<String>, // The error from the last attempt to load a theme
+    game_file_path: String,   // The file path entered by the user to save or load the game
+    #[serde(skip)]
+    game_file_message: String, // The result of the last attempt to save or load the game
+    history: History,         // Every finished game, for the statistics panel
+    #[serde(skip)]
+    show_stats: bool, // Flag indicating whether the statistics window is open
+    leaderboard: Leaderboard, // The highest-scoring games, shown when a game ends
+    daily_attempt: Option<DailyAttempt>, // The last daily puzzle the profile played, which can only be played once
+    before_daily: Option<(GameSettings, Vec<String>)>, // The settings and hot-seat players from before the daily puzzle, for the next new game
+    #[serde(skip)]
+    confirm_daily: bool, // Flag indicating whether the dialog to leave the game in progress for the daily puzzle should be shown
+    #[serde(skip)]
+    daily_error: Option<String>, // Why the daily puzzle could not be started
+    #[serde(skip)]
+    leaderboard_rank: Option<usize>, // The position the last finished game took on the leaderboard
+    #[serde(skip)]
+    profile: Option<String>, // The name of the open profile, or `None` while one is being chosen
+    #[serde(skip)]
+    profiles: Profiles, // Every player profile
+    #[serde(skip)]
+    new_profile_name: String, // The name entered by the user for a new profile
+    #[serde(skip)]
+    profile_error: Option<String>, // Why the last new profile could not be created
+    server_address: String, // The multiplayer server address entered by the user
+    online_room: String,    // The multiplayer room entered by the user
+    #[serde(skip)]
+    online: Option<OnlineGame>, // The game joined on a multiplayer server, if any
+    #[serde(skip)]
+    online_error: Option<String>, // Why the last attempt to join or stay on a server failed
+    #[serde(skip)]
+    server_override: Option<String>, // The server address given on the command line, used by the first profile opened
+    #[serde(skip)]
+    words_override: Option<PathBuf>, // The word source given on the command line, used by the first profile opened
+    #[serde(skip)]
+    seed_override: Option<u64>, // The seed given on the command line, set up in the first profile opened
+    #[serde(skip)]
+    puzzle_override: Option<PuzzleCode>, // The puzzle given on the command line, set up in the first profile opened
+    #[serde(skip)]
+    seed_text: String, // The seed entered on the setup screen, or empty for a random one
+    #[serde(skip)]
+    puzzle_text: String, // The puzzle code entered on the setup screen
+    #[serde(skip)]
+    seed_error: Option<String>, // Why the entered seed or puzzle code can't be used
+}
+
+impl Default for HangmanApp {
+    fn default() -> Self {
+        Self {
+            game_state: HangmanGameState::default(),
+            settings: GameSettings::default(),
+            phase: GamePhase::MainMenu,
+            show_confirmation_dialog: false,
+            allowed_to_close: false,
+            input_text: String::new(),
+            submitted_text: String::new(),
+            input_phrase: String::new(),
+            show_input_phrase: false,
+            input_clue: String::new(),
+            phrase_error: None,
+            new_player_name: String::new(),
+            handing_off: false,
+            ui_debug: false,
+            word_source: embedded_word_source(Language::default()),
+            word_source_path: String::new(),
+            word_source_error: None,
+            custom_word_source: false,
+            guess_message: String::new(),
+            solve_text: String::new(),
+            themes: Theme::built_in(),
+            theme: 0,
+            theme_path: String::new(),
+            theme_files: Vec::new(),
+            theme_error: None,
+            game_file_path: "hangman-save.json".to_string(),
+            game_file_message: String::new(),
+            history: History::default(),
+            show_stats: false,
+            leaderboard: Leaderboard::default(),
+            daily_attempt: None,
+            before_daily: None,
+            confirm_daily: false,
+            daily_error: None,
+            leaderboard_rank: None,
+            profile: None,
+            profiles: Profiles::default(),
+            new_profile_name: String::new(),
+            profile_error: None,
+            server_address: DEFAULT_ADDRESS.to_string(),
+            online_room: "lobby".to_string(),
+            online: None,
+            online_error: None,
+            server_override: None,
+            words_override: None,
+            seed_override: None,
+            puzzle_override: None,
+            seed_text: String::new(),
+            puzzle_text: String::new(),
+            seed_error: None,
+        }
+    }
+}
+
+impl HangmanApp {
+    fn new(cc: &eframe::CreationContext<'_>, cli_options: CliOptions) -> Self {
+        // Customize egui here with cc.egui_ctx.set_fonts and cc.egui_ctx.set_visuals.
+        // Use the cc.gl (a glow::Context) to create graphics shaders and buffers that you can use
+        // for e.g. egui::PaintCallback.
+        // The profiles are listed on startup, and the player's own state is restored once they pick one
+        let profiles = cc
+            .storage
+            .and_then(|storage| {
+                eframe::get_value(storage, PROFILES_KEY).or_else(|| {
+                    // State saved before there were profiles becomes the first profile
+                    storage.get_string(eframe::APP_KEY).map(|_| Profiles {
+                        names: vec![LEGACY_PROFILE.to_string()],
+                        last: Some(LEGACY_PROFILE.to_string()),
+                    })
+                })
+            })
+            .unwrap_or_default();
+
+        HangmanApp {
+            profiles,
+            words_override: cli_options.words,
+            seed_override: cli_options.seed,
+            puzzle_override: cli_options.puzzle,
+            server_override: cli_options.server,
+            ..Default::default()
+        }
+    }
+
+    /// Open a player profile, restoring the state it was last saved with. The open profile is
+    /// saved first.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the profile to open.
+    /// * `frame` - The frame whose storage the profiles are saved in.
+    ///
+    fn open_profile(&mut self, name: String, frame: &mut eframe::Frame) {
+        if let Some(storage) = frame.storage_mut() {
+            eframe::App::save(self, storage);
+        }
+
+        let saved: Option<HangmanApp> = frame.storage().and_then(|storage| {
+            eframe::get_value(storage, &profile_key(&name)).or_else(|| {
+                (name == LEGACY_PROFILE)
+                    .then(|| eframe::get_value(storage, eframe::APP_KEY))
+                    .flatten()
+            })
+        });
+        let mut app = saved.unwrap_or_else(|| HangmanApp {
+            // Keep each player's game file apart
+            game_file_path: format!(
+                "hangman-save-{}.json",
+                name.chars()
+                    .map(|c| if c.is_alphanumeric() { c } else { '-' })
+                    .collect::<String>()
+            ),
+            ..Default::default()
+        });
+
+        // Only the paths of the word source and themes are saved, so load them again
+        let game_state = std::mem::take(&mut app.game_state);
+        if app.custom_word_source {
+            app.custom_word_source = false;
+            app.load_word_source();
+        }
+        app.restore_game(game_state);
+        let theme = app.theme;
+        for path in std::mem::take(&mut app.theme_files) {
+            app.theme_path = path;
+            app.load_theme();
+        }
+        app.theme = theme.min(app.themes.len() - 1);
+
+        if let Some(path) = self.words_override.take() {
+            app.word_source_path = path.display().to_string();
+            app.load_word_source();
+        }
+
+        if let Some(address) = self.server_override.take() {
+            app.server_address = address;
+        }
+
+        if let Some(puzzle) = self.puzzle_override.take() {
+            app.new_game();
+            app.use_puzzle(puzzle);
+        }
+        if let Some(seed) = self.seed_override.take() {
+            if app.phase != GamePhase::Setup {
+                app.new_game();
+            }
+            app.seed_text = seed.to_string();
+        }
+
+        app.profiles = std::mem::take(&mut self.profiles);
+        app.profiles.last = Some(name.clone());
+        app.profile = Some(name);
+        *self = app;
+    }
+
+    /// Create a player profile named `new_profile_name` and open it.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame whose storage the profiles are saved in.
+    ///
+    fn create_profile(&mut self, frame: &mut eframe::Frame) {
+        let name = self.new_profile_name.trim().to_string();
+        if name.is_empty() {
+            self.profile_error = Some("Please enter a name for the profile.".to_string());
+        } else if self.profiles.names.contains(&name) {
+            self.profile_error = Some(format!("There is already a profile called {}.", name));
+        } else {
+            self.profiles.names.push(name.clone());
+            self.new_profile_name.clear();
+            self.profile_error = None;
+            self.open_profile(name, frame);
+        }
+    }
+
+    /// Show the list of player profiles to pick from, and a way to create a new one.
+    fn show_profiles_window(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        egui::Window::new("Who's playing?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                let mut chosen = None;
+                for name in &self.profiles.names {
+                    let last = self.profiles.last.as_ref() == Some(name);
+                    if ui.selectable_label(last, name).clicked() {
+                        chosen = Some(name.clone());
+                    }
+                }
+                if let Some(name) = chosen {
+                    self.open_profile(name, frame);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("New profile:");
+                    let response = ui.text_edit_singleline(&mut self.new_profile_name);
+                    let entered =
+                        response.lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.button("Create").clicked() || entered {
+                        self.create_profile(frame);
+                    }
+                });
+                if let Some(error) = &self.profile_error {
+                    ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+                }
+            });
+    }
+
+    /// Create a fresh game that picks its phrase from the selected word source, for the same
+    /// players and with the same settings except the difficulty, which is chosen again. After a
+    /// daily puzzle, the settings and players from before it come back.
+    fn new_game_state(&mut self) -> HangmanGameState {
+        let mut builder = self.game_state.next_game();
+        if let Some((settings, players)) = self.before_daily.take() {
+            self.settings = settings;
+            builder = builder.players(players);
+        }
+        builder
+            .settings(GameSettings {
+                difficulty: None,
+                ..self.settings.clone()
+            })
+            .word_source(self.word_source.clone())
+            .build()
+    }
+
+    /// Load the word file or directory in `word_source_path` and use it for the current game.
+    fn load_word_source(&mut self) {
+        match open_word_source(self.word_source_path.trim()) {
+            Ok(word_source) => {
+                self.word_source = word_source;
+                self.word_source_error = None;
+                self.custom_word_source = true;
+                // Keep the category only if the new source has it too
+                if !self
+                    .settings
+                    .category
+                    .as_ref()
+                    .is_some_and(|category| self.word_source.categories().contains(category))
+                {
+                    self.settings.category = None;
+                }
+            }
+            Err(error) => {
+                self.word_source_error = Some(format!(
+                    "Could not load {}: {}",
+                    self.word_source_path.trim(),
+                    error
+                ));
+            }
+        }
+        self.game_state.set_word_source(self.word_source.clone());
+    }
+
+    /// Load the theme file in `theme_path` and draw the hangman with it.
+    fn load_theme(&mut self) {
+        match Theme::open(self.theme_path.trim()) {
+            Ok(theme) => {
+                // Loading a theme again replaces the earlier copy instead of adding another
+                self.theme = match self.themes.iter().position(|t| t.name == theme.name) {
+                    Some(position) => {
+                        self.themes[position] = theme;
+                        position
+                    }
+                    None => {
+                        self.themes.push(theme);
+                        self.themes.len() - 1
+                    }
+                };
+                let path = self.theme_path.trim().to_string();
+                if !self.theme_files.contains(&path) {
+                    self.theme_files.push(path);
+                }
+                self.theme_error = None;
+            }
+            Err(error) => {
+                self.theme_error = Some(format!(
+                    "Could not load {}: {}",
+                    self.theme_path.trim(),
+                    error
+                ));
+            }
+        }
+    }
+
+    /// Continue a saved game, picking its next phrases from the selected word source.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The saved game.
+    ///
+    fn restore_game(&mut self, mut game_state: HangmanGameState) {
+        // The embedded words follow the language of the saved game
+        if !self.custom_word_source {
+            self.word_source = embedded_word_source(game_state.language());
+        }
+        // This drops a category the word source doesn't have, such as one from a directory that is gone
+        game_state.set_word_source(self.word_source.clone());
+        self.settings = game_state.settings();
+        if game_state.daily().is_none() {
+            self.before_daily = None;
+        }
+        self.game_state = game_state;
+    }
+
+    /// Save the current game to the file in `game_file_path`.
+    fn save_game_file(&mut self) {
+        let path = self.game_file_path.trim();
+        self.game_file_message = match self.game_state.save_to_file(path) {
+            Ok(()) => format!("Game saved to {}", path),
+            Err(error) => format!("Could not save to {}: {}", path, error),
+        };
+    }
+
+    /// Replace the current game with the one saved in the file in `game_file_path`.
+    fn load_game_file(&mut self) {
+        let path = self.game_file_path.trim().to_string();
+        let today = Day::today();
+        match HangmanGameState::load_from_file(&path) {
+            // Playing a finished daily puzzle again would give it a second attempt
+            Ok(game_state)
+                if game_state
+                    .daily()
+                    .is_some_and(|day| self.daily_finished(day)) =>
+            {
+                self.game_file_message = format!(
+                    "Could not load {}: the daily puzzle it holds was already played",
+                    path
+                );
+            }
+            // So would today's puzzle saved by another profile, or earlier by this one
+            Ok(game_state)
+                if game_state.daily() == Some(today)
+                    && (self.daily_attempt.as_ref())
+                        .is_some_and(|attempt| attempt.day == today) =>
+            {
+                self.game_file_message = format!(
+                    "Could not load {}: today's daily puzzle was already started on this profile",
+                    path
+                );
+            }
+            Ok(game_state) => {
+                if let Some(day) = game_state.daily() {
+                    if self.before_daily.is_none() {
+                        self.keep_settings_before_daily();
+                    }
+                    // Loading today's puzzle counts as the profile's attempt, like starting it
+                    if day == today {
+                        self.daily_attempt = Some(DailyAttempt {
+                            day,
+                            summary: daily_summary(&game_state),
+                        });
+                    }
+                }
+                self.restore_game(game_state);
+                self.guess_message.clear();
+                self.leaderboard_rank = None;
+                self.phase = match self.game_state.status() {
+                    GameStatus::Setup => GamePhase::Setup,
+                    GameStatus::Playing => GamePhase::Playing,
+                    GameStatus::Won | GameStatus::Lost => GamePhase::RoundOver,
+                };
+                self.game_state.resume();
+                self.game_file_message = format!("Game loaded from {}", path);
+            }
+            Err(error) => {
+                self.game_file_message = format!("Could not load {}: {}", path, error);
+            }
+        }
+    }
+
+    /// Show the statistics over every finished game, and the most recent games.
+    fn show_stats_window(&mut self, ctx: &egui::Context) {
+        let stats = self.history.stats();
+
+        egui::Window::new("Statistics")
+            .open(&mut self.show_stats)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if stats.games == 0 {
+                    ui.label("No games finished yet.");
+                    return;
+                }
+
+                ui.label(format!(
+                    "Games played: {}    Won: {}    Win rate: {:.0}%",
+                    stats.games,
+                    stats.wins,
+                    stats.win_rate() * 100.0
+                ));
+                ui.label(format!(
+                    "Current streak: {}    Best streak: {}",
+                    stats.current_streak, stats.best_streak
+                ));
+
+                ui.separator();
+                ui.label("Average misses:");
+                egui::Grid::new("average_misses").show(ui, |ui| {
+                    for (difficulty, average) in &stats.average_misses {
+                        ui.label(difficulty.name());
+                        ui.label(format!(
+                            "{:.1} of {}",
+                            average,
+                            difficulty.profile().allowed_misses
+                        ));
+                        ui.end_row();
+                    }
+                });
+
+                if !stats.most_missed_letters.is_empty() {
+                    ui.separator();
+                    ui.label(format!(
+                        "Most missed letters: {}",
+                        stats
+                            .most_missed_letters
+                            .iter()
+                            .take(5)
+                            .map(|(letter, count)| format!("{} ({})", letter, count))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ));
+                }
+
+                ui.separator();
+                ui.label("Recent games:");
+                egui::Grid::new("recent_games")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for record in self.history.records.iter().rev().take(10) {
+                            ui.label(if record.win { "Won" } else { "Lost" });
+                            ui.label(&record.phrase);
+                            ui.label(record.difficulty.name());
+                            ui.label(format!("{} misses", record.incorrect_guess_count));
+                            ui.label(format!("{}s", record.duration.as_secs()));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Add a hot-seat player named `new_player_name` to the game being set up.
+    fn add_player(&mut self) {
+        let name = self.new_player_name.trim().to_string();
+        if !name.is_empty() {
+            self.game_state.add_player(name);
+            self.new_player_name.clear();
+        }
+    }
+
+    /// Connect to the server in `server_address` and join `online_room`: as a player under the
+    /// profile's name, creating the room at the selected difficulty if nobody is in it, or as a
+    /// spectator.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context to repaint when a message from the server arrives.
+    /// * `spectator` - Whether to only watch the room.
+    ///
+    fn join_online(&mut self, ctx: &egui::Context, spectator: bool) {
+        let address = self.server_address.trim().to_string();
+        let ctx = ctx.clone();
+        let room = self.online_room.trim().to_string();
+        let join = if spectator {
+            ClientMessage::Watch { room }
+        } else {
+            ClientMessage::Join {
+                room,
+                name: self.profile.clone().unwrap_or_default(),
+                difficulty: self.settings.difficulty,
+            }
+        };
+
+        let result = Connection::connect(address.as_str(), move || ctx.request_repaint())
+            .and_then(|mut connection| connection.send(&join).map(|()| connection));
+        match result {
+            Ok(connection) => {
+                self.online = Some(OnlineGame {
+                    connection,
+                    address,
+                    spectator,
+                    view: None,
+                    message: String::new(),
+                    chat: Vec::new(),
+                    chat_text: String::new(),
+                    guess_text: String::new(),
+                    solve_text: String::new(),
+                });
+                self.online_error = None;
+            }
+            Err(error) => {
+                self.online_error = Some(format!("Could not join {}: {}", address, error));
+            }
+        }
+    }
+
+    /// Show the game joined on a server: the room's game as the server last sent it, the
+    /// scoreboard and the chat. Whatever the player does is sent to the server.
+    fn show_online_game(&mut self, ctx: &egui::Context) {
+        let Some(online) = &mut self.online else {
+            return;
+        };
+
+        // Pick up everything the server sent since the last frame
+        loop {
+            match online.connection.try_receive() {
+                Ok(Some(ServerMessage::Joined { room, name })) => {
+                    online.message = format!("Joined {} as {}.", room, name);
+                }
+                Ok(Some(ServerMessage::Watching { room })) => {
+                    online.message = format!("Watching {}.", room);
+                }
+                Ok(Some(ServerMessage::StateUpdate { message, state })) => {
+                    online.message = message;
+                    online.view = Some(state);
+                }
+                Ok(Some(ServerMessage::Chat { from, text })) => online.chat.push((from, text)),
+                Ok(Some(ServerMessage::GameOver {
+                    win,
+                    phrase,
+                    winners,
+                })) => {
+                    online.message = match (win, winners.as_slice()) {
+                        (false, _) => format!("Game over! The phrase was {}.", phrase),
+                        (true, []) => format!("Solved! The phrase was {}.", phrase),
+                        (true, winners) => format!(
+                            "Solved! The phrase was {}. {} won.",
+                            phrase,
+                            winners.join(" and ")
+                        ),
+                    };
+                }
+                Ok(Some(ServerMessage::Error { message })) => online.message = message,
+                Ok(None) => break,
+                Err(error) => {
+                    self.online_error = Some(format!("Left {}: {}", online.address, error));
+                    self.online = None;
+                    return;
+                }
+            }
+        }
+
+        let display_state = online.view.as_ref().map(GameView::display_state);
+        let mut sent = Vec::new();
+        let mut leave = false;
+
+        if let Some(game_state) = &display_state {
+            show_scoreboard(ctx, game_state);
+        }
+
+        egui::TopBottomPanel::bottom("chat").show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(120.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for (from, text) in &online.chat {
+                        ui.label(format!("{}: {}", from, text));
+                    }
+                });
+            if online.spectator {
+                return;
+            }
+            ui.horizontal(|ui| {
+                ui.label("Chat:");
+                let response = ui.text_edit_singleline(&mut online.chat_text);
+                let entered =
+                    response.lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter));
+                if (ui.button("Send").clicked() || entered) && !online.chat_text.trim().is_empty() {
+                    sent.push(ClientMessage::Chat {
+                        text: std::mem::take(&mut online.chat_text),
+                    });
+                }
+            });
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Rusty Hangman Game!");
+            ui.horizontal(|ui| {
+                if online.spectator {
+                    ui.label(format!("Watching at {}", online.address));
+                } else {
+                    ui.label(format!("Online at {}", online.address));
+                }
+                leave = ui.button("Leave").clicked();
+            });
+            let Some(game_state) = &display_state else {
+                ui.label("Waiting for the server...");
+                return;
+            };
+
+            ui.horizontal(|ui| {
+                egui::Frame::dark_canvas(ui.style()).show(ui, |ui| {
+                    ui.label("Guesses:");
+                    for row in game_state.language().keyboard_rows(13) {
+                        ui.horizontal(|ui| {
+                            for letter in row.iter() {
+                                let missed = online
+                                    .view
+                                    .as_ref()
+                                    .is_some_and(|view| view.missed_letters.contains(letter));
+                                if missed {
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(255, 0, 0),
+                                        letter.to_string(),
+                                    );
+                                } else if game_state.guessed_letters().contains(letter) {
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(0, 255, 0),
+                                        letter.to_string(),
+                                    );
+                                } else if online.spectator {
+                                    ui.label(letter.to_string());
+                                } else if ui.link(letter.to_string()).clicked() {
+                                    sent.push(ClientMessage::Guess { letter: *letter });
+                                }
+                            }
+                        });
+                    }
+                });
+                egui::Frame::dark_canvas(ui.style()).show(ui, |ui| {
+                    let (response, painter) =
+                        ui.allocate_painter(ui.max_rect().size(), egui::Sense::hover());
+                    let theme = &self.themes[self.theme];
+                    theme.render(
+                        theme.stage(game_state),
+                        &mut EguiRenderer {
+                            painter: &painter,
+                            center: response.rect.center(),
+                        },
+                    );
+                });
+            });
+
+            ui.horizontal(|ui| {
+                if let Some(player) = game_state.current_player() {
+                    if !game_state.is_over() {
+                        ui.strong(format!("{}'s turn.", player.name));
+                    }
+                }
+                ui.heading(format!(
+                    "Phrase to guess: {}",
+                    game_state.obfuscated_phrase()
+                ));
+                if let Some(category) = game_state.category() {
+                    ui.label(format!("Category: {}", category_display_name(category)));
+                }
+            });
+            if !online.message.is_empty() {
+                ui.label(&online.message);
+            }
+            if online.spectator {
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Enter a letter:");
+                ui.add_sized(
+                    egui::Vec2::new(30.0, 25.0),
+                    egui::TextEdit::singleline(&mut online.guess_text),
+                );
+                if ui.button("Guess").clicked() {
+                    if let Some(letter) = online.guess_text.chars().next() {
+                        sent.push(ClientMessage::Guess { letter });
+                    }
+                    online.guess_text.clear();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Solve the phrase:");
+                ui.text_edit_singleline(&mut online.solve_text);
+                if ui.button("Solve").clicked() && !online.solve_text.trim().is_empty() {
+                    sent.push(ClientMessage::Solve {
+                        attempt: std::mem::take(&mut online.solve_text),
+                    });
+                }
+            });
+            if game_state.is_over() && ui.button("New Game").clicked() {
+                sent.push(ClientMessage::NewGame);
+            }
+        });
+
+        for message in sent {
+            if let Err(error) = online.connection.send(&message) {
+                self.online_error = Some(format!("Left {}: {}", online.address, error));
+                leave = true;
+                break;
+            }
+        }
+        if leave {
+            self.online = None;
+        }
+    }
+
+    /// Check the setter's phrase and, if it can be played, move on to the hand-off screen.
+    fn submit_setter_phrase(&mut self) {
+        if self.settings.difficulty.is_none() {
+            self.phrase_error = Some("Please choose a difficulty first.".to_string());
+            return;
+        }
+
+        self.game_state.configure(self.settings.clone());
+        match self.game_state.check_phrase(&self.input_phrase) {
+            Ok(()) => {
+                self.phrase_error = None;
+                self.show_input_phrase = false;
+                self.handing_off = true;
+            }
+            Err(error) => self.phrase_error = Some(error.message()),
+        }
+    }
+
+    /// Start the guesser's game with the phrase and hint entered by the setter.
+    fn start_setter_game(&mut self) {
+        let phrase = std::mem::take(&mut self.input_phrase);
+        let clue = std::mem::take(&mut self.input_clue);
+        let clue = Some(clue.trim().to_string()).filter(|clue| !clue.is_empty());
+        match self.game_state.start_with_phrase(phrase, clue) {
+            Ok(()) => self.phase = GamePhase::Playing,
+            Err(error) => self.phrase_error = Some(error.message()),
+        }
+        self.handing_off = false;
+        self.guess_message.clear();
+    }
+
+    /// Go back to the word source compiled into the game for the current language.
+    fn use_embedded_word_source(&mut self) {
+        self.word_source = embedded_word_source(self.settings.language);
+        self.word_source_error = None;
+        self.custom_word_source = false;
+        self.settings.category = None;
+        self.game_state.set_word_source(self.word_source.clone());
+    }
+
+    /// Leave the menu or the last round to set up a new game, for the same players and with
+    /// the same settings except the difficulty.
+    fn new_game(&mut self) {
+        self.game_state = self.new_game_state();
+        self.settings = self.game_state.settings();
+        self.guess_message.clear();
+        self.solve_text.clear();
+        self.phrase_error = None;
+        self.leaderboard_rank = None;
+        self.seed_text.clear();
+        self.puzzle_text.clear();
+        self.seed_error = None;
+        self.phase = GamePhase::Setup;
+    }
+
+    /// Set the game being set up to play a shared puzzle, with its settings and seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle` - The puzzle's code.
+    ///
+    fn use_puzzle(&mut self, puzzle: PuzzleCode) {
+        // The embedded words follow the puzzle's language
+        self.settings.language = puzzle.settings.language;
+        if !self.custom_word_source {
+            self.use_embedded_word_source();
+        }
+        self.settings = puzzle.settings.clone();
+        self.seed_text = puzzle.seed.to_string();
+        self.puzzle_text = puzzle.to_string();
+        self.seed_error = None;
+    }
+
+    /// Start the game that was set up with a random phrase from the word source. Does nothing
+    /// until a difficulty is chosen.
+    fn start_game(&mut self) {
+        if self.settings.difficulty.is_none() {
+            return;
+        }
+        self.game_state.configure(self.settings.clone());
+
+        let seed = self.seed_text.trim();
+        if !seed.is_empty() {
+            match seed.parse() {
+                Ok(seed) => {
+                    self.game_state.reseed(seed);
+                }
+                Err(_) => {
+                    self.seed_error = Some(format!("{} is not a seed.", seed));
+                    return;
+                }
+            }
+        }
+        self.seed_error = None;
+        if let Err(error) = self.game_state.start() {
+            self.word_source_error = Some(format!("The game can't start: {}", error));
+            return;
+        }
+        self.phase = GamePhase::Playing;
+    }
+
+    /// End the round once the game is won or lost: record it in the history, put it on the
+    /// leaderboard if it scored high enough, and show how it went.
+    fn finish_round(&mut self) {
+        self.leaderboard_rank = if self.history.record(&self.game_state) {
+            self.leaderboard.submit(&self.game_state)
+        } else {
+            None
+        };
+        if let Some(attempt) = &mut self.daily_attempt {
+            if self.game_state.daily() == Some(attempt.day) && attempt.summary.is_none() {
+                attempt.summary = daily_summary(&self.game_state);
+            }
+        }
+        self.phase = GamePhase::RoundOver;
+    }
+
+    /// Start today's daily puzzle, in the current language and with the current word source.
+    /// Does nothing if the profile has already played it.
+    fn start_daily(&mut self) {
+        let day = Day::today();
+        if self
+            .daily_attempt
+            .as_ref()
+            .is_some_and(|attempt| attempt.day == day)
+        {
+            return;
+        }
+
+        let mut game_state = HangmanGameState::builder()
+            .language(self.settings.language)
+            .word_source(self.word_source.clone())
+            .daily(day)
+            .build();
+        if let Err(error) = game_state.start() {
+            self.daily_error = Some(format!("The daily puzzle can't start: {}", error));
+            return;
+        }
+        self.daily_error = None;
+
+        self.keep_settings_before_daily();
+        self.game_state = game_state;
+        // The attempt counts from the start, so leaving the puzzle doesn't give another go
+        self.daily_attempt = Some(DailyAttempt { day, summary: None });
+        self.guess_message.clear();
+        self.solve_text.clear();
+        self.leaderboard_rank = None;
+        self.phase = GamePhase::Playing;
+    }
+
+    /// Keep the settings and players to go back to after a daily puzzle.
+    fn keep_settings_before_daily(&mut self) {
+        self.before_daily = Some((
+            self.settings.clone(),
+            self.game_state
+                .players()
+                .iter()
+                .map(|player| player.name.clone())
+                .collect(),
+        ));
+    }
+
+    /// Whether the profile has finished the daily puzzle of a day.
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - The day of the puzzle.
+    ///
+    fn daily_finished(&self, day: Day) -> bool {
+        self.daily_attempt
+            .as_ref()
+            .is_some_and(|attempt| attempt.day == day && attempt.summary.is_some())
+    }
+
+    /// Close the app without asking.
+    fn quit(&mut self, ctx: &egui::Context) {
+        self.show_confirmation_dialog = false;
+        self.allowed_to_close = true;
+        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    }
+
+    /// Show the main menu: continue the game or start a new one, play online, save or load the
+    /// game, and the statistics and profile.
+    fn show_main_menu(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let mut switch_profile = false;
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Rusty Hangman Game!");
+            ui.horizontal(|ui| {
+                if self.game_state.status() == GameStatus::Playing
+                    && ui.button("Continue").clicked()
+                {
+                    self.game_state.resume();
+                    self.phase = GamePhase::Playing;
+                }
+                if ui.button("New Game").clicked() {
+                    self.new_game();
+                }
+                if ui.button("Quit").clicked() {
+                    self.quit(ctx);
+                }
+            });
+            ui.horizontal(|ui| match &self.daily_attempt {
+                Some(attempt) if attempt.day == Day::today() => match &attempt.summary {
+                    Some(summary) => {
+                        ui.label("Today's daily puzzle is done. Come back tomorrow!");
+                        show_daily_summary(ui, summary);
+                    }
+                    None if self.game_state.daily() == Some(attempt.day) => {
+                        ui.label("Today's daily puzzle is under way. Continue it to finish.");
+                    }
+                    None => {
+                        ui.label("Today's daily puzzle was left unfinished. Come back tomorrow!");
+                    }
+                },
+                _ => {
+                    if ui
+                        .button("Daily Puzzle")
+                        .on_hover_text(format!(
+                            "Everyone with the same words gets the same phrase today, on {}. \
+                             It can only be played once.",
+                            DAILY_DIFFICULTY.name()
+                        ))
+                        .clicked()
+                    {
+                        if self.game_state.status() == GameStatus::Playing {
+                            self.confirm_daily = true;
+                        } else {
+                            self.start_daily();
+                        }
+                    }
+                    if let Some(error) = &self.daily_error {
+                        ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Online:");
+                ui.text_edit_singleline(&mut self.server_address)
+                    .on_hover_text("The address of a server started with rusty-hangman-server");
+                ui.label("Room:");
+                ui.text_edit_singleline(&mut self.online_room);
+                if ui.button("Join").clicked() {
+                    self.join_online(ctx, false);
+                }
+                if ui
+                    .button("Watch")
+                    .on_hover_text("Follow the room's game without playing")
+                    .clicked()
+                {
+                    self.join_online(ctx, true);
+                }
+                if let Some(error) = &self.online_error {
+                    ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Game file:");
+                ui.text_edit_singleline(&mut self.game_file_path);
+                if ui.button("Save Game").clicked() {
+                    self.save_game_file();
+                }
+                if ui.button("Load Game").clicked() {
+                    self.load_game_file();
+                }
+                if !self.game_file_message.is_empty() {
+                    ui.label(&self.game_file_message);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.show_stats, "Show Statistics");
+                ui.checkbox(&mut self.ui_debug, "Show Debug Info");
+                ui.separator();
+                ui.label(format!(
+                    "Profile: {}",
+                    self.profile.as_deref().unwrap_or_default()
+                ));
+                switch_profile = ui.button("Switch Profile").clicked();
+            });
+        });
+
+        if self.confirm_daily {
+            egui::Window::new("Leave the game in progress for the daily puzzle?")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button("No").clicked() {
+                            self.confirm_daily = false;
+                        }
+                        if ui.button("Yes").clicked() {
+                            self.confirm_daily = false;
+                            self.start_daily();
+                        }
+                    });
+                });
+        }
+
+        if switch_profile {
+            // Save the profile now, as the one picked next replaces it
+            if let Some(storage) = frame.storage_mut() {
+                eframe::App::save(self, storage);
+            }
+            self.profile = None;
+        }
+    }
 
-    eframe::run_native(
-        "Rusty Hangman Game!",
-        options,
-        Box::new(|cc| Box::new(HangmanApp::new(cc))),
-    )
-    .expect("Failed to run native application! Panic!");
-}
+    /// Show the settings of the game being set up, and the phrase entry for two players. The
+    /// game starts once a difficulty is chosen, with Start or by handing the setter's phrase over.
+    fn show_setup(&mut self, ctx: &egui::Context) {
+        if self.handing_off {
+            // Only this screen is shown until the guesser takes over, so nothing gives the phrase away
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.heading("Rusty Hangman Game!");
+                ui.label("The phrase is set. Hand over to the guesser, then press Start.");
+                ui.horizontal(|ui| {
+                    if ui.button("Start").clicked() {
+                        self.start_setter_game();
+                    }
+                    if ui.button("Back to the Setter").clicked() {
+                        self.handing_off = false;
+                    }
+                });
+            });
+            return;
+        }
 
-#[derive(Default)]
-/// Represents a Hangman application.
-struct HangmanApp {
-    game_state: HangmanGameState,   // The current state of the Hangman game
-    show_confirmation_dialog: bool, // Flag indicating whether the exit confirmation dialog should be shown
-    allowed_to_close: bool,         // Flag indicating whether the application is allowed to close
-    input_text: String,             // The text entered by the user as input for guessing
-    submitted_text: String,         // The text submitted by the user/program as a guess
-    input_phrase: String,           // The phrase entered by the user as input for the game
-    ui_debug: bool,                 // Flag indicating whether debug information should be shown
-}
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Rusty Hangman Game!");
+            ui.horizontal(|ui| {
+                ui.label("Select difficulty:");
+                for difficulty in Difficulty::ALL {
+                    let profile = difficulty.profile();
+                    if ui
+                        .selectable_label(
+                            self.settings.difficulty == Some(difficulty),
+                            difficulty.name(),
+                        )
+                        .on_hover_text(format!(
+                            "{} misses allowed, {}-{} letter words, {} hints",
+                            profile.allowed_misses,
+                            profile.words.length.start(),
+                            profile.words.length.end(),
+                            profile.hint_budget
+                        ))
+                        .clicked()
+                    {
+                        self.settings.difficulty = Some(difficulty);
+                    }
+                }
 
-impl HangmanApp {
-    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        // Customize egui here with cc.egui_ctx.set_fonts and cc.egui_ctx.set_visuals.
-        // Restore app state using cc.storage (requires the "persistence" feature).
-        // Use the cc.gl (a glow::Context) to create graphics shaders and buffers that you can use
-        // for e.g. egui::PaintCallback.
-        Self::default();
-        Self {
-            game_state: HangmanGameState::new("".to_string().to_uppercase()),
-            show_confirmation_dialog: false,
-            allowed_to_close: false,
-            input_text: String::new(),
-            submitted_text: String::new(),
-            input_phrase: String::new(),
-            ui_debug: false,
-        }
+                let categories = self.word_source.categories();
+                if !categories.is_empty() {
+                    ui.separator();
+                    ui.label("Category:");
+                    egui::ComboBox::from_id_source("category")
+                        .selected_text(
+                            self.settings
+                                .category
+                                .as_deref()
+                                .map_or("Any".to_string(), category_display_name),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.settings.category, None, "Any");
+                            for category in categories {
+                                let name = category_display_name(&category);
+                                ui.selectable_value(
+                                    &mut self.settings.category,
+                                    Some(category),
+                                    name,
+                                );
+                            }
+                        });
+                }
+
+                ui.separator();
+                ui.label("Wrong solve:");
+                egui::ComboBox::from_id_source("solve_penalty")
+                    .selected_text(self.settings.solve_penalty.name())
+                    .show_ui(ui, |ui| {
+                        for penalty in SolvePenalty::CHOICES {
+                            ui.selectable_value(
+                                &mut self.settings.solve_penalty,
+                                penalty,
+                                penalty.name(),
+                            );
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label("Hot seat players:");
+                let mut removed = None;
+                for (position, player) in self.game_state.players().iter().enumerate() {
+                    if ui
+                        .button(&player.name)
+                        .on_hover_text("Remove this player")
+                        .clicked()
+                    {
+                        removed = Some(position);
+                    }
+                }
+                if let Some(position) = removed {
+                    self.game_state.remove_player(position);
+                }
+                let response = ui.text_edit_singleline(&mut self.new_player_name);
+                if ui.button("Add Player").clicked()
+                    || (response.lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter)))
+                {
+                    self.add_player();
+                }
+                if !self.game_state.players().is_empty() {
+                    ui.separator();
+                    egui::ComboBox::from_id_source("miss_limit")
+                        .selected_text(self.settings.miss_limit.name())
+                        .show_ui(ui, |ui| {
+                            for miss_limit in MissLimit::ALL {
+                                ui.selectable_value(
+                                    &mut self.settings.miss_limit,
+                                    miss_limit,
+                                    miss_limit.name(),
+                                );
+                            }
+                        });
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(format!("Word source: {}", self.word_source.name()));
+                ui.text_edit_singleline(&mut self.word_source_path)
+                    .on_hover_text("A word file, or a directory of category files");
+                if ui.button("Load").clicked() {
+                    self.load_word_source();
+                }
+                if ui.button("Use Embedded List").clicked() {
+                    self.use_embedded_word_source();
+                }
+
+                ui.separator();
+                ui.label("Language:");
+                let language = self.settings.language;
+                egui::ComboBox::from_id_source("language")
+                    .selected_text(language.name())
+                    .show_ui(ui, |ui| {
+                        for language in Language::ALL {
+                            ui.selectable_value(
+                                &mut self.settings.language,
+                                language,
+                                language.name(),
+                            );
+                        }
+                    });
+                if self.settings.language != language {
+                    self.settings.fold_accents = self.settings.language.folds_accents();
+                    // Switch to the new language's embedded words unless the user loaded their own
+                    if !self.custom_word_source {
+                        self.use_embedded_word_source();
+                    }
+                }
+                ui.checkbox(&mut self.settings.fold_accents, "Ignore accents")
+                    .on_hover_text(
+                        "Guessing a letter also reveals its accented forms \
+                         that are not on the keyboard",
+                    );
+            });
+            ui.horizontal(|ui| {
+                ui.label("Theme:");
+                egui::ComboBox::from_id_source("theme")
+                    .selected_text(&self.themes[self.theme].name)
+                    .show_ui(ui, |ui| {
+                        for (position, theme) in self.themes.iter().enumerate() {
+                            ui.selectable_value(&mut self.theme, position, &theme.name);
+                        }
+                    });
+                ui.text_edit_singleline(&mut self.theme_path)
+                    .on_hover_text("A theme file describing the shapes drawn at each stage");
+                if ui.button("Load Theme").clicked() {
+                    self.load_theme();
+                }
+            });
+            if let Some(error) = &self.theme_error {
+                ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+            }
+            ui.horizontal(|ui| {
+                let auto_reveal = &mut self.settings.auto_reveal;
+                ui.label("Show from the start:");
+                ui.checkbox(&mut auto_reveal.punctuation, "Punctuation")
+                    .on_hover_text("Apostrophes, hyphens, full stops and the like");
+                ui.checkbox(&mut auto_reveal.digits, "Digits");
+                ui.checkbox(&mut auto_reveal.symbols, "Symbols")
+                    .on_hover_text("Characters such as &, # and +");
+            });
+            if let Some(error) = &self.word_source_error {
+                ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+            }
+            ui.horizontal(|ui| {
+                ui.label("Seed:");
+                ui.text_edit_singleline(&mut self.seed_text)
+                    .on_hover_text("Leave empty for a random phrase");
+                ui.label("Puzzle code:");
+                ui.text_edit_singleline(&mut self.puzzle_text)
+                    .on_hover_text("A code shared by another player, to play their puzzle");
+                if ui.button("Use Code").clicked() {
+                    match self.puzzle_text.parse() {
+                        Ok(puzzle) => self.use_puzzle(puzzle),
+                        Err(error) => self.seed_error = Some(error),
+                    }
+                }
+            });
+            if let Some(error) = &self.seed_error {
+                ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        self.settings.difficulty.is_some(),
+                        egui::Button::new("Start"),
+                    )
+                    .on_disabled_hover_text("Choose a difficulty first")
+                    .clicked()
+                {
+                    self.start_game();
+                }
+                if ui.button("Back").clicked() {
+                    self.phase = GamePhase::MainMenu;
+                }
+            });
+            ui.separator();
+            ui.label("Two players: the setter enters a phrase for the guesser, who looks away.");
+            let phrase_response = ui
+                .horizontal(|ui| {
+                    ui.label("Phrase:");
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.input_phrase)
+                            .password(!self.show_input_phrase),
+                    );
+                    ui.checkbox(&mut self.show_input_phrase, "Show");
+                    response
+                })
+                .inner;
+            ui.horizontal(|ui| {
+                ui.label("Hint for the guesser (optional):");
+                ui.text_edit_singleline(&mut self.input_clue);
+            });
+            if let Some(error) = &self.phrase_error {
+                ui.colored_label(egui::Color32::from_rgb(255, 0, 0), error);
+            }
+            if ui.button("Submit").clicked()
+                || (phrase_response.lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter)))
+            {
+                self.submit_setter_phrase();
+            }
+        });
     }
-}
-const ALPHABET: [char; 26] = [
-    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
-    'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
-];
-/// Implementation of the `eframe::App` trait for the `HangmanApp` struct.
-impl eframe::App for HangmanApp {
-    /// Updates the application state and renders the user interface.
-    ///
-    /// # Arguments
-    ///
-    /// * `ctx` - The `egui::Context` used for rendering the UI.
-    /// * `frame` - The `eframe::Frame` used for displaying the UI.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+
+    /// Show the game being played. Once it is won or lost, the round is over.
+    fn show_playing(&mut self, ctx: &egui::Context) {
+        if !self.game_state.players().is_empty() {
+            show_scoreboard(ctx, &self.game_state);
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Rusty Hangman Game!");
 
@@ -82,27 +1457,30 @@ impl eframe::App for HangmanApp {
                     egui::Frame::dark_canvas(ui.style()).show(ui, |ui| {
                         // TODO: Display the phrase to guess with underscores for each letter.
                         ui.label("Guesses:");
-                        ui.horizontal(|ui| {
-                            for letter in ALPHABET.iter() {
-                                if self.game_state.chars_to_guess.contains(letter)
-                                    && self.game_state.guessed_letters.contains(letter)
-                                {
-                                    ui.colored_label(
-                                        egui::Color32::from_rgb(0, 255, 0),
-                                        format!("{}", *letter),
-                                    );
-                                } else if self.game_state.guessed_letters.contains(letter) {
-                                    ui.colored_label(
-                                        egui::Color32::from_rgb(255, 0, 0),
-                                        format!("{}", *letter),
-                                    );
-                                } else {
-                                    if ui.link(format!("{}", *letter)).clicked() {
-                                        self.submitted_text = letter.to_string();
+                        // An on-screen keyboard generated from the alphabet of the current language
+                        for row in self.game_state.language().keyboard_rows(13) {
+                            ui.horizontal(|ui| {
+                                for letter in row.iter() {
+                                    if self.game_state.chars_to_guess().contains(letter)
+                                        && self.game_state.guessed_letters().contains(letter)
+                                    {
+                                        ui.colored_label(
+                                            egui::Color32::from_rgb(0, 255, 0),
+                                            format!("{}", *letter),
+                                        );
+                                    } else if self.game_state.guessed_letters().contains(letter) {
+                                        ui.colored_label(
+                                            egui::Color32::from_rgb(255, 0, 0),
+                                            format!("{}", *letter),
+                                        );
+                                    } else {
+                                        if ui.link(format!("{}", *letter)).clicked() {
+                                            self.submitted_text = letter.to_string();
+                                        }
                                     }
                                 }
-                            }
-                        });
+                            });
+                        }
                     });
                 });
                 // ui.add_space(ui.available_size_before_wrap().x * 0.50);
@@ -110,49 +1488,16 @@ impl eframe::App for HangmanApp {
                     egui::Frame::dark_canvas(ui.style()).show(ui, |ui| {
                         let max_rect = ui.max_rect();
                         // Allocate a painter
-                        // Allocate a painter
                         let (response, painter) =
                             ui.allocate_painter(max_rect.size(), egui::Sense::hover());
 
-                        // Head
-                        painter.circle_filled(
-                            response.rect.center() + egui::Vec2::new(0.0, -70.0),
-                            20.0,
-                            egui::Color32::WHITE,
-                        );
-
-                        // Body
-                        painter.line_segment(
-                            [
-                                response.rect.center() + egui::Vec2::new(0.0, -50.0),
-                                response.rect.center() + egui::Vec2::new(0.0, -10.0),
-                            ],
-                            egui::Stroke::new(2.0, egui::Color32::WHITE),
-                        );
-
-                        // Arms
-                        painter.line_segment(
-                            [
-                                response.rect.center() + egui::Vec2::new(-20.0, -30.0),
-                                response.rect.center() + egui::Vec2::new(20.0, -30.0),
-                            ],
-                            egui::Stroke::new(2.0, egui::Color32::WHITE),
-                        );
-
-                        // Legs
-                        painter.line_segment(
-                            [
-                                response.rect.center() + egui::Vec2::new(-10.0, -10.0),
-                                response.rect.center() + egui::Vec2::new(-10.0, 10.0),
-                            ],
-                            egui::Stroke::new(2.0, egui::Color32::WHITE),
-                        );
-                        painter.line_segment(
-                            [
-                                response.rect.center() + egui::Vec2::new(10.0, -10.0),
-                                response.rect.center() + egui::Vec2::new(10.0, 10.0),
-                            ],
-                            egui::Stroke::new(2.0, egui::Color32::WHITE),
+                        let theme = &self.themes[self.theme];
+                        theme.render(
+                            theme.stage(&self.game_state),
+                            &mut EguiRenderer {
+                                painter: &painter,
+                                center: response.rect.center(),
+                            },
                         );
                         // ui.add_space(450.0);
                     });
@@ -161,106 +1506,138 @@ impl eframe::App for HangmanApp {
             ui.add_space(ui.available_size_before_wrap().y * 0.10);
 
             ui.horizontal(|ui| {
+                match self.game_state.current_player() {
+                    Some(player) if !self.game_state.is_over() => {
+                        ui.strong(format!("{}'s turn.", player.name));
+                    }
+                    _ => {}
+                }
                 ui.label("Enter a letter:");
-                ui.add_sized(
+                let response = ui.add_sized(
                     egui::Vec2::new(30.0, 25.0),
                     egui::TextEdit::singleline(&mut self.input_text),
                 );
-                if self.input_text.len() > 1 {
+                // Only Enter in the letter field guesses, so Enter in the solve box doesn't too
+                let guess_entered =
+                    response.lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter));
+                // Truncate by characters rather than bytes, so letters outside ASCII don't split
+                if self.input_text.chars().count() > 1 {
                     println!("Input text is too long, truncating...");
-                    self.input_text.truncate(1);
+                    self.input_text = self.input_text.chars().take(1).collect();
                     println!("Input text after truncation: {}", self.input_text);
                 }
-                if ui.button("Guess").clicked() || ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+                if ui.button("Guess").clicked() || guess_entered {
                     if let Some(letter) = self.input_text.chars().next() {
-                        self.submitted_text = letter.to_string().to_uppercase();
+                        self.submitted_text = letter.to_string();
                         self.input_text.clear();
                     }
                 }
+                // Keep typing letters after guessing one with Enter
+                if guess_entered {
+                    response.request_focus();
+                }
+                if self.game_state.difficulty().is_some()
+                    && ui
+                        .add_enabled(
+                            self.game_state.hints_left() > 0 && !self.game_state.is_over(),
+                            egui::Button::new(format!(
+                                "Hint ({} left)",
+                                self.game_state.hints_left()
+                            )),
+                        )
+                        .clicked()
+                {
+                    self.guess_message = match self.game_state.use_hint() {
+                        Some(letter) => format!("Hint: {} is in the phrase.", letter),
+                        None => "No hints left.".to_string(),
+                    };
+                }
                 ui.heading(format!(
                     "Phrase to guess: {}",
-                    self.game_state.obfuscated_phrase
-                ))
+                    self.game_state.obfuscated_phrase()
+                ));
+                if let Some(category) = self.game_state.category() {
+                    ui.label(format!("Category: {}", category_display_name(category)));
+                }
+                if let Some(clue) = self.game_state.clue() {
+                    ui.label(format!("Setter's hint: {}", clue));
+                }
             });
 
-            if !self.submitted_text.is_empty() {
-                println!("Guessing letter: {}", self.submitted_text);
-                ui.label(format!("Guessed letter: {}", self.submitted_text));
-                hangmangame::HangmanGameState::guess_letter(
-                    &mut self.game_state,
-                    self.submitted_text
-                        .to_uppercase()
-                        .chars()
-                        .next()
-                        .expect("Invalid character guessed!"),
-                );
+            ui.horizontal(|ui| {
+                ui.label("Solve the phrase:");
+                let response = ui.text_edit_singleline(&mut self.solve_text);
+                let solve_entered =
+                    response.lost_focus() && ctx.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui
+                    .add_enabled(
+                        !self.solve_text.trim().is_empty() && !self.game_state.is_over(),
+                        egui::Button::new("Solve"),
+                    )
+                    .on_hover_text(format!(
+                        "A wrong answer: {}",
+                        self.game_state.solve_penalty().name().to_lowercase()
+                    ))
+                    .clicked()
+                    || (solve_entered && !self.solve_text.trim().is_empty())
+                {
+                    let outcome = self.game_state.solve_phrase(&self.solve_text);
+                    self.guess_message = outcome.message().to_string();
+                    self.solve_text.clear();
+                }
+            });
+
+            if let Some(letter) = self.submitted_text.chars().next() {
+                // The game state uppercases the guess itself, so `ß` isn't turned into `SS`
+                let outcome = self.game_state.guess_letter(letter);
+                self.guess_message = outcome.message(letter);
                 self.submitted_text.clear();
             }
-            if self.game_state.difficulty == 0 {
-                ui.horizontal(|ui| {
-                    ui.label("Select difficulty:");
-                    if ui.button("Very Easy").clicked() {
-                        self.game_state.difficulty = 10;
-                    }
-                    if ui.button("Easy").clicked() {
-                        self.game_state.difficulty = 8;
-                    }
-                    if ui.button("Normal").clicked() {
-                        self.game_state.difficulty = 6;
-                    }
-                    if ui.button("Hard").clicked() {
-                        self.game_state.difficulty = 4;
-                    }
-                });
+            if !self.guess_message.is_empty() {
+                ui.label(&self.guess_message);
             }
-            if self.game_state.phrase_to_guess == "" {
-                ui.horizontal(|ui| {
-                    ui.label("Please enter a phrase/word for the game:");
-                    ui.text_edit_singleline(&mut self.input_phrase);
-                });
-                if ui.button("Submit").clicked() || ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    self.game_state
-                        .update_guess_phrase(self.input_phrase.clone());
-                    self.input_phrase.clear();
+            ui.horizontal(|ui| {
+                if ui.button("Pause").clicked() {
+                    self.game_state.pause();
+                    self.phase = GamePhase::Paused;
                 }
-            }
+                if ui.button("Main Menu").clicked() {
+                    // The clock stops until the game is continued
+                    self.game_state.pause();
+                    self.phase = GamePhase::MainMenu;
+                }
+                ui.separator();
+                show_puzzle_code(ui, &self.game_state);
+            });
             // ! DEBUG CODE AHEAD!!!!
-            self.game_state.show_debug = self.ui_debug;
-
-            ui.checkbox(&mut self.ui_debug, "Show Debug Info");
             if self.ui_debug {
                 ui.vertical(|ui| {
                     ui.heading("Debug Info:");
-                    ui.label(format!(
-                        "Phrase to guess: {}",
-                        self.game_state.phrase_to_guess
-                    ));
+                    ui.label(format!("Phrase to guess: {}", self.game_state.phrase()));
                     ui.label(format!(
                         "Chars to guess: {:?}",
-                        self.game_state.chars_to_guess
+                        self.game_state.chars_to_guess()
                     ));
                     ui.label(format!(
                         "Obfuscated phrase: {}",
-                        self.game_state.obfuscated_phrase
+                        self.game_state.obfuscated_phrase()
                     ));
                     ui.label(format!("Submitted Text: {}", self.submitted_text));
                     ui.label(format!("Input Text: {}", self.input_text));
                     ui.label(format!("Input Phrase: {}", self.input_phrase));
                     ui.label(format!(
                         "Guessed letters: {:?}",
-                        self.game_state.guessed_letters
+                        self.game_state.guessed_letters()
                     ));
                     ui.label(format!(
                         "Incorrect guess count: {}",
-                        self.game_state.incorrect_guess_count
-                    ));
-                    ui.label(format!(
-                        "Guesses Left: {}",
-                        self.game_state.difficulty - self.game_state.incorrect_guess_count
+                        self.game_state.incorrect_guess_count()
                     ));
-                    ui.label(format!("Difficulty: {}", self.game_state.difficulty));
-                    ui.label(format!("Game over: {}", self.game_state.game_over));
-                    ui.label(format!("Win: {}", self.game_state.win));
+                    ui.label(format!("Guesses Left: {}", self.game_state.guesses_left()));
+                    ui.label(format!("Hints used: {}", self.game_state.hints_used()));
+                    ui.label(format!("Difficulty: {:?}", self.game_state.difficulty()));
+                    ui.label(format!("Category: {:?}", self.game_state.category()));
+                    ui.label(format!("Status: {:?}", self.game_state.status()));
                 });
             }
             // ! END DEBUG CODE!!!!
@@ -283,37 +1660,162 @@ impl eframe::App for HangmanApp {
             //// }
         });
 
-        if self.game_state.game_over {
-            egui::Window::new("Game Over!")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
-                    ui.vertical(|ui| {
-                        if self.game_state.win {
-                            ui.label("Congratulations! You won!");
-                            ui.label(format!(
-                                "The phrase was: {}",
-                                self.game_state.phrase_to_guess
-                            ));
-                        } else {
-                            ui.label("Game Over! You lost!");
-                            ui.label(format!(
-                                "The phrase was: {}",
-                                self.game_state.phrase_to_guess
-                            ));
-                        }
-                    });
-                    ui.horizontal(|ui| {
-                        if ui.button("Restart?").clicked() {
-                            self.game_state = default::Default::default();
-                        }
-                        if ui.button("Quit?").clicked() {
-                            self.show_confirmation_dialog = false;
-                            self.allowed_to_close = true;
-                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-                        }
-                    });
+        if self.game_state.is_over() {
+            self.finish_round();
+        }
+    }
+
+    /// Show the paused screen, which hides the phrase and the hangman until the game is resumed.
+    fn show_paused(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Rusty Hangman Game!");
+            ui.label("Paused.");
+            ui.horizontal(|ui| {
+                if ui.button("Resume").clicked() {
+                    self.game_state.resume();
+                    self.phase = GamePhase::Playing;
+                }
+                if ui.button("Main Menu").clicked() {
+                    self.phase = GamePhase::MainMenu;
+                }
+            });
+        });
+    }
+
+    /// Show how the round ended: the phrase, the winners, the score and the high scores.
+    fn show_round_over(&mut self, ctx: &egui::Context) {
+        if !self.game_state.players().is_empty() {
+            show_scoreboard(ctx, &self.game_state);
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Rusty Hangman Game!");
+            ui.vertical(|ui| {
+                if self.game_state.is_won() {
+                    ui.label("Congratulations! You won!");
+                    ui.label(format!("The phrase was: {}", self.game_state.phrase()));
+                } else {
+                    ui.label("Game Over! You lost!");
+                    ui.label(format!("The phrase was: {}", self.game_state.phrase()));
+                }
+
+                let leaders = self.game_state.leaders();
+                match leaders.as_slice() {
+                    [] => {}
+                    [winner] => {
+                        ui.label(format!(
+                            "{} wins with {} points!",
+                            winner.name, winner.score
+                        ));
+                    }
+                    [first, ..] => {
+                        ui.label(format!(
+                            "It's a tie between {} with {} points!",
+                            leaders
+                                .iter()
+                                .map(|player| player.name.as_str())
+                                .collect::<Vec<&str>>()
+                                .join(" and "),
+                            first.score
+                        ));
+                    }
+                }
+
+                let score = self.game_state.score();
+                ui.label(format!(
+                    "Score: {} ({} letters + {} misses left + {} time, x{:.1})",
+                    score.total,
+                    score.letter_points,
+                    score.misses_bonus,
+                    score.time_bonus,
+                    score.multiplier as f32 / 100.0
+                ));
+                if let Some(rank) = self.leaderboard_rank {
+                    ui.label(format!("New high score! #{}", rank + 1));
+                }
+
+                ui.separator();
+                ui.label("High scores:");
+                egui::Grid::new("leaderboard").striped(true).show(ui, |ui| {
+                    for (position, entry) in self.leaderboard.entries.iter().enumerate() {
+                        let text = |text: String| {
+                            if Some(position) == self.leaderboard_rank {
+                                egui::RichText::new(text).strong()
+                            } else {
+                                egui::RichText::new(text)
+                            }
+                        };
+                        ui.label(text(format!("#{}", position + 1)));
+                        ui.label(text(entry.score.to_string()));
+                        ui.label(text(entry.phrase.clone()));
+                        ui.label(text(entry.difficulty.name().to_string()));
+                        ui.label(text(if entry.win { "Won" } else { "Lost" }.to_string()));
+                        ui.end_row();
+                    }
                 });
+            });
+            match daily_summary(&self.game_state) {
+                Some(summary) => show_daily_summary(ui, &summary),
+                None => show_puzzle_code(ui, &self.game_state),
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Play Again").clicked() {
+                    self.new_game();
+                }
+                if ui.button("Statistics").clicked() {
+                    self.show_stats = true;
+                }
+                if ui.button("Main Menu").clicked() {
+                    self.phase = GamePhase::MainMenu;
+                }
+                if ui.button("Quit").clicked() {
+                    self.quit(ctx);
+                }
+            });
+        });
+    }
+}
+/// Implementation of the `eframe::App` trait for the `HangmanApp` struct.
+impl eframe::App for HangmanApp {
+    /// Save the open profile's game and settings, and the list of profiles, so they are restored
+    /// the next time the app starts.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The storage eframe keeps the app state in.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        if let Some(profile) = &self.profile {
+            eframe::set_value(storage, &profile_key(profile), self);
+        }
+        eframe::set_value(storage, PROFILES_KEY, &self.profiles);
+    }
+
+    /// Updates the application state and renders the user interface.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The `egui::Context` used for rendering the UI.
+    /// * `frame` - The `eframe::Frame` used for displaying the UI.
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if self.profile.is_none() {
+            self.show_profiles_window(ctx, frame);
+            return;
+        }
+        if self.online.is_some() {
+            self.show_online_game(ctx);
+            return;
+        }
+
+        match self.phase {
+            GamePhase::MainMenu => self.show_main_menu(ctx, frame),
+            GamePhase::Setup => self.show_setup(ctx),
+            GamePhase::Playing => self.show_playing(ctx),
+            GamePhase::RoundOver => self.show_round_over(ctx),
+            GamePhase::Paused => self.show_paused(ctx),
+        }
+
+        if self.show_stats {
+            self.show_stats_window(ctx);
         }
 
         if ctx.input(|i| i.viewport().close_requested()) {
@@ -336,12 +1838,197 @@ impl eframe::App for HangmanApp {
                             self.allowed_to_close = false;
                         }
                         if ui.button("Yes").clicked() {
-                            self.show_confirmation_dialog = false;
-                            self.allowed_to_close = true;
-                            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                            self.quit(ctx);
                         }
                     });
                 });
         }
     }
 }
+
+/// Show the players of a hot-seat or online game with their scores and misses, marking whose turn it is.
+fn show_scoreboard(ctx: &egui::Context, game_state: &HangmanGameState) {
+    egui::SidePanel::right("scoreboard").show(ctx, |ui| {
+        ui.heading("Scoreboard");
+        egui::Grid::new("scoreboard_players")
+            .striped(true)
+            .show(ui, |ui| {
+                for (position, player) in game_state.players().iter().enumerate() {
+                    let playing = position == game_state.turn() && !game_state.is_over();
+                    ui.label(if playing { ">" } else { "" });
+                    ui.label(&player.name);
+                    ui.label(format!("{} points", player.score));
+                    match game_state.miss_limit() {
+                        MissLimit::Shared => ui.label(format!("{} misses", player.misses)),
+                        MissLimit::PerPlayer if game_state.is_out(player) => ui.label("Out"),
+                        MissLimit::PerPlayer => ui.label(format!(
+                            "{} of {} misses",
+                            player.misses,
+                            game_state.allowed_misses()
+                        )),
+                    };
+                    ui.end_row();
+                }
+            });
+        if game_state.miss_limit() == MissLimit::Shared && game_state.difficulty().is_some() {
+            ui.label(format!(
+                "Shared misses: {} of {}",
+                game_state.incorrect_guess_count(),
+                game_state.allowed_misses()
+            ));
+        }
+    });
+}
+
+/// Show the seed and the code to share the game's puzzle with, which can be copied to the
+/// clipboard. Nothing is shown for a phrase entered by a player, or for a daily puzzle, whose
+/// result summary is shared instead.
+fn show_puzzle_code(ui: &mut egui::Ui, game_state: &HangmanGameState) {
+    let Some(code) = game_state.puzzle_code() else {
+        return;
+    };
+    if game_state.daily().is_some() {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(format!("Seed: {}", game_state.seed()));
+        ui.label(format!("Puzzle code: {}", code));
+        if ui
+            .button("Copy Code")
+            .on_hover_text("Share the code to let someone else play this puzzle")
+            .clicked()
+        {
+            ui.output_mut(|output| output.copied_text = code.to_string());
+        }
+    });
+}
+
+/// Show a daily puzzle's result summary, which can be copied to the clipboard.
+fn show_daily_summary(ui: &mut egui::Ui, summary: &str) {
+    ui.vertical(|ui| {
+        ui.label(summary);
+        if ui
+            .button("Copy Result")
+            .on_hover_text("Share how it went without giving the phrase away")
+            .clicked()
+        {
+            ui.output_mut(|output| output.copied_text = summary.to_string());
+        }
+    });
+}
+
+/// Draws theme shapes with an egui painter, centered on an area of the screen.
+struct EguiRenderer<'a> {
+    painter: &'a egui::Painter, // The painter to draw with
+    center: egui::Pos2,         // The screen position of the center of the drawing
+}
+
+impl EguiRenderer<'_> {
+    /// Convert a point of the drawing to a screen position.
+    fn pos(&self, (x, y): Point) -> egui::Pos2 {
+        self.center + egui::Vec2::new(x, y)
+    }
+}
+
+impl HangmanRenderer for EguiRenderer<'_> {
+    fn draw_shape(&mut self, shape: &Shape) {
+        let color = |[r, g, b]: Rgb| egui::Color32::from_rgb(r, g, b);
+        match *shape {
+            Shape::Line {
+                from,
+                to,
+                width,
+                color: rgb,
+            } => {
+                self.painter.line_segment(
+                    [self.pos(from), self.pos(to)],
+                    egui::Stroke::new(width, color(rgb)),
+                );
+            }
+            Shape::Circle {
+                center,
+                radius,
+                color: rgb,
+            } => {
+                self.painter
+                    .circle_filled(self.pos(center), radius, color(rgb));
+            }
+            Shape::Ring {
+                center,
+                radius,
+                width,
+                color: rgb,
+            } => {
+                self.painter.circle_stroke(
+                    self.pos(center),
+                    radius,
+                    egui::Stroke::new(width, color(rgb)),
+                );
+            }
+            Shape::Rect {
+                min,
+                max,
+                color: rgb,
+            } => {
+                self.painter.rect_filled(
+                    egui::Rect::from_two_pos(self.pos(min), self.pos(max)),
+                    0.0,
+                    color(rgb),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won_game(phrase: &str) -> HangmanGameState {
+        let mut game_state = HangmanGameState::new(phrase.to_string()).unwrap();
+        for letter in phrase.chars() {
+            game_state.guess_letter(letter);
+        }
+        game_state
+    }
+
+    #[test]
+    fn profile_with_a_corrupt_game_keeps_its_history() {
+        let mut app = HangmanApp::default();
+        assert!(app.history.record(&won_game("CAT")));
+        app.game_state = won_game("DOG");
+
+        let mut profile = serde_json::to_value(&app).unwrap();
+        profile["game_state"]["win"] = serde_json::Value::Bool(false);
+        let restored: HangmanApp = serde_json::from_value(profile).unwrap();
+
+        assert_eq!(restored.history.records.len(), 1);
+        assert_eq!(restored.history.records[0].phrase, "CAT");
+        assert_eq!(restored.game_state.status(), GameStatus::Setup);
+    }
+
+    #[test]
+    fn loading_todays_daily_puzzle_counts_as_the_attempt() {
+        let day = Day::today();
+        let mut daily = HangmanGameState::builder().daily(day).build();
+        daily.start().unwrap();
+        let path = std::env::temp_dir().join(format!("rusty-hangman-daily-{}.json", day.0));
+        daily.save_to_file(&path).unwrap();
+
+        let mut app = HangmanApp {
+            game_file_path: path.display().to_string(),
+            ..Default::default()
+        };
+        app.load_game_file();
+        assert_eq!(app.game_state.daily(), Some(day));
+        assert_eq!(app.daily_attempt, Some(DailyAttempt { day, summary: None }));
+
+        // Loading it again, or into a profile that already started it, would be another attempt
+        app.game_state = HangmanGameState::default();
+        app.load_game_file();
+        assert_eq!(app.game_state.daily(), None);
+        assert!(app.game_file_message.starts_with("Could not load"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
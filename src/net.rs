@@ -0,0 +1,297 @@
+//! The protocol spoken between the multiplayer server and its clients.
+//!
+//! Clients connect over TCP and exchange JSON messages, one per line, each with a `type` field
+//! naming it. The server owns the game of every room; clients only send what their player does
+//! and draw the state the server sends back.
+//!
+//! A client joins a room first, naming its player. The room is created with the given
+//! difficulty (`VeryEasy`, `Easy`, `Normal` or `Hard`, `Normal` if left out) if nobody is in
+//! it yet:
+//!
+//! ```text
+//! {"type":"join","room":"lobby","name":"Ann","difficulty":"Hard"}
+//! ```
+//!
+//! The players of a room take turns like in a hot-seat game, one guess or solve attempt each.
+//! A game starts with its first guess or solve attempt; players who join after that play from
+//! the next game.
+//! Anyone can chat, and anyone can start a new game once the last one is over:
+//!
+//! ```text
+//! {"type":"guess","letter":"E"}
+//! {"type":"solve","attempt":"HELLO WORLD"}
+//! {"type":"chat","text":"Good luck!"}
+//! {"type":"new_game"}
+//! ```
+//!
+//! A client can watch a room instead, for example to show a game on a big screen. Spectators
+//! can't guess, solve, chat or start games, and are never sent the phrase, not even once the
+//! game is over:
+//!
+//! ```text
+//! {"type":"watch","room":"lobby"}
+//! ```
+//!
+//! The server confirms the join, then sends the whole state of the room (see `GameView`) to
+//! everyone in it whenever it changes, with a line saying what changed. Chat is passed on to
+//! everyone in the room, the end of a game is announced to its players, and a message the
+//! server can't act on, such as a guess out of turn, is answered with an error for its sender
+//! only:
+//!
+//! ```text
+//! {"type":"joined","room":"lobby","name":"Ann"}
+//! {"type":"watching","room":"lobby"}
+//! {"type":"state_update","message":"Ann: Good guess! E is in the phrase once.","state":{"obfuscated_phrase":"_ E _ _ _ ",...}}
+//! {"type":"chat","from":"Ann","text":"Good luck!"}
+//! {"type":"game_over","win":true,"phrase":"HELLO","winners":["Ann"]}
+//! {"type":"error","message":"It's Bob's turn."}
+//! ```
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::alphabet::Language;
+use crate::difficulty::Difficulty;
+use crate::hangmangame::HangmanGameState;
+use crate::players::{MissLimit, Player};
+
+/// The address the server listens on, and clients join, unless told otherwise.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+/// A message from a client to the server.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Join a room as a player, creating it if it doesn't exist.
+    Join {
+        room: String,
+        name: String,
+        #[serde(default)]
+        difficulty: Option<Difficulty>,
+    },
+    /// Watch a room as a spectator.
+    Watch { room: String },
+    /// Guess a letter, on the player's turn.
+    Guess { letter: char },
+    /// Try to solve the whole phrase, on the player's turn.
+    Solve { attempt: String },
+    /// Say something to everyone in the room.
+    Chat { text: String },
+    /// Start a new game in the room, once the last one is over.
+    NewGame,
+}
+
+/// A message from the server to a client.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// The client joined a room under a name.
+    Joined { room: String, name: String },
+    /// The client is watching a room.
+    Watching { room: String },
+    /// The state of the room's game, with what changed.
+    StateUpdate { message: String, state: GameView },
+    /// Someone in the room said something.
+    Chat { from: String, text: String },
+    /// The room's game ended. The winners are the players with the most points, if the phrase
+    /// was guessed.
+    GameOver {
+        win: bool,
+        phrase: String,
+        winners: Vec<String>,
+    },
+    /// The client's last message could not be acted on.
+    Error { message: String },
+}
+
+/// What a client may know about a game: everything on screen, but not the phrase until the
+/// game is over, and never for spectators.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameView {
+    /// The phrase with the characters that are still hidden replaced with underscores.
+    pub obfuscated_phrase: String,
+    /// The letters that have been guessed, in order.
+    pub guessed_letters: Vec<char>,
+    /// The guessed letters that are not in the phrase, in order.
+    pub missed_letters: Vec<char>,
+    /// The count of incorrect guesses.
+    pub incorrect_guess_count: u32,
+    /// The difficulty of the game.
+    pub difficulty: Option<Difficulty>,
+    /// The language whose alphabet the game is played with.
+    pub language: Language,
+    /// The category the phrase came from, if any.
+    pub category: Option<String>,
+    /// The players, in the order they take turns.
+    pub players: Vec<Player>,
+    /// The position in `players` of the player whose turn it is.
+    pub turn: usize,
+    /// How the players' incorrect guesses count against the allowed misses.
+    pub miss_limit: MissLimit,
+    /// Flag indicating if the game is over.
+    pub game_over: bool,
+    /// Flag indicating if the game was won.
+    pub win: bool,
+    /// The phrase, once the game is over, for players only.
+    pub phrase: Option<String>,
+}
+
+impl GameView {
+    /// Make the view of a game that its players are sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game.
+    ///
+    pub fn from_game(game_state: &HangmanGameState) -> Self {
+        let phrase = game_state
+            .is_over()
+            .then(|| game_state.phrase().to_string());
+        GameView::with_phrase(game_state, phrase)
+    }
+
+    /// Make the view of a game that spectators are sent, which never has the phrase.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game.
+    ///
+    pub fn for_spectators(game_state: &HangmanGameState) -> Self {
+        GameView::with_phrase(game_state, None)
+    }
+
+    /// Make the view of a game, with the phrase if it may be seen.
+    fn with_phrase(game_state: &HangmanGameState, phrase: Option<String>) -> Self {
+        GameView {
+            obfuscated_phrase: game_state.obfuscated_phrase().to_string(),
+            guessed_letters: game_state.guessed_letters().to_vec(),
+            missed_letters: game_state
+                .guessed_letters()
+                .iter()
+                .filter(|letter| !game_state.chars_to_guess().contains(letter))
+                .copied()
+                .collect(),
+            incorrect_guess_count: game_state.incorrect_guess_count(),
+            difficulty: game_state.difficulty(),
+            language: game_state.language(),
+            category: game_state.category().map(str::to_string),
+            players: game_state.players().to_vec(),
+            turn: game_state.turn(),
+            miss_limit: game_state.miss_limit(),
+            game_over: game_state.is_over(),
+            win: game_state.is_won(),
+            phrase,
+        }
+    }
+
+    /// A game state with what the view shows, for drawing it with a `Theme` or reusing a
+    /// front-end's widgets. It has no phrase to guess, so it can't be played.
+    pub fn display_state(&self) -> HangmanGameState {
+        HangmanGameState::from_view(self)
+    }
+}
+
+/// Write a message as one line of JSON.
+///
+/// # Arguments
+///
+/// * `writer` - Where to write the message, such as a `TcpStream`.
+/// * `message` - The message to write.
+///
+/// # Returns
+///
+/// * `Result<(), io::Error>` - An error if the message could not be written.
+pub fn write_message<T: Serialize>(writer: &mut impl Write, message: &T) -> Result<(), io::Error> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()
+}
+
+/// A client's connection to the server. Messages from the server are read on a thread of their
+/// own and picked up with `try_receive`.
+pub struct Connection {
+    stream: TcpStream,
+    incoming: Receiver<Result<ServerMessage, io::Error>>,
+}
+
+impl Connection {
+    /// Connect to a server.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the server, such as `127.0.0.1:7878`.
+    /// * `on_message` - Called on the reading thread after each message arrives, to wake up the front-end.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Connection, io::Error>` - The connection, or an error if the server could not be reached.
+    pub fn connect(
+        address: impl ToSocketAddrs,
+        on_message: impl Fn() + Send + 'static,
+    ) -> Result<Self, io::Error> {
+        let stream = TcpStream::connect(address)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let (sender, incoming) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let message = line.and_then(|line| {
+                    serde_json::from_str(&line)
+                        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+                });
+                let failed = message.is_err();
+                if sender.send(message).is_err() {
+                    break;
+                }
+                on_message();
+                if failed {
+                    break;
+                }
+            }
+            on_message();
+        });
+
+        Ok(Connection { stream, incoming })
+    }
+
+    /// Send a message to the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to send.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), io::Error>` - An error if the connection is broken.
+    pub fn send(&mut self, message: &ClientMessage) -> Result<(), io::Error> {
+        write_message(&mut self.stream, message)
+    }
+
+    /// Take the next message that has arrived from the server, if any.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<ServerMessage>, io::Error>` - The message, `None` if there is none yet, or an error once the connection is closed or the server sent something that isn't a message.
+    pub fn try_receive(&self) -> Result<Option<ServerMessage>, io::Error> {
+        match self.incoming.try_recv() {
+            Ok(message) => message.map(Some),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "The server closed the connection",
+            )),
+        }
+    }
+}
+
+impl Drop for Connection {
+    /// Close the connection, which also ends the reading thread.
+    fn drop(&mut self) {
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// A player of a hot-seat game, where several players take turns guessing the same phrase.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Player {
+    /// The player's name, as shown on the scoreboard.
+    pub name: String,
+    /// The points the player earned by revealing letters.
+    pub score: u32,
+    /// The number of incorrect guesses the player made, including the cost of wrong solve attempts.
+    pub misses: u32,
+}
+
+impl Player {
+    /// Create a player who hasn't played yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The player's name.
+    ///
+    pub fn new(name: String) -> Self {
+        Player {
+            name,
+            ..Default::default()
+        }
+    }
+}
+
+/// How the incorrect guesses of a hot-seat game count against the allowed misses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MissLimit {
+    /// Every player's misses count towards one limit, and the game is lost for everyone when it is reached.
+    #[default]
+    Shared,
+    /// Each player has the full number of misses. A player who runs out sits out the rest of
+    /// the game, which is lost once every player has run out.
+    PerPlayer,
+}
+
+impl MissLimit {
+    /// Every way of counting misses, in the order they are offered to the players.
+    pub const ALL: [MissLimit; 2] = [MissLimit::Shared, MissLimit::PerPlayer];
+
+    /// The way of counting misses, as shown to the players.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MissLimit::Shared => "Shared misses",
+            MissLimit::PerPlayer => "Misses per player",
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::alphabet::{AutoReveal, Language};
+use crate::difficulty::Difficulty;
+use crate::hangmangame::{GameSettings, SolvePenalty};
+
+/// A code to share a puzzle with: the seed its phrase was picked with and the settings it is
+/// played with. Another player who starts a game from the code gets the same phrase, as long
+/// as they pick from the same word source (such as the embedded word list).
+///
+/// Codes are written as the seed in 16 hexadecimal digits, then one digit each for the
+/// difficulty, the language, the character options (a hexadecimal digit) and the solve penalty,
+/// then the category if there is one, like `00000000075bcd15-2031` or
+/// `00000000075bcd15-2031-animals`. A penalty of more than 9 misses is written as 9.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleCode {
+    /// The seed the phrase is picked with.
+    pub seed: u64,
+    /// The settings the puzzle is played with.
+    pub settings: GameSettings,
+}
+
+/// The bits of the character options digit.
+const FOLD_ACCENTS: u32 = 1;
+const REVEAL_PUNCTUATION: u32 = 2;
+const REVEAL_DIGITS: u32 = 4;
+const REVEAL_SYMBOLS: u32 = 8;
+
+/// The most misses the solve penalty digit can stand for.
+const MAX_PENALTY: u32 = 9;
+
+/// The number of hexadecimal digits the seed is written with.
+const SEED_DIGITS: usize = 16;
+
+impl fmt::Display for PuzzleCode {
+    /// Write the code as it is shared.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let settings = &self.settings;
+        let difficulty = settings.difficulty.unwrap_or_default();
+        let difficulty = Difficulty::ALL
+            .iter()
+            .position(|d| *d == difficulty)
+            .unwrap_or_default();
+        let language = Language::ALL
+            .iter()
+            .position(|l| *l == settings.language)
+            .unwrap_or_default();
+        let options = [
+            (settings.fold_accents, FOLD_ACCENTS),
+            (settings.auto_reveal.punctuation, REVEAL_PUNCTUATION),
+            (settings.auto_reveal.digits, REVEAL_DIGITS),
+            (settings.auto_reveal.symbols, REVEAL_SYMBOLS),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, bit)| bit)
+        .sum::<u32>();
+        // A wrong solve that ends the game is 0, otherwise the number of misses it costs
+        let penalty = match settings.solve_penalty {
+            SolvePenalty::Misses(misses) => misses.clamp(1, MAX_PENALTY),
+            SolvePenalty::EndGame => 0,
+        };
+
+        write!(
+            f,
+            "{:016x}-{}{}{:x}{}",
+            self.seed, difficulty, language, options, penalty
+        )?;
+        if let Some(category) = settings.category.as_deref().filter(|c| !c.is_empty()) {
+            write!(f, "-{}", category)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PuzzleCode {
+    type Err = String;
+
+    /// Read a shared code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code, as shared.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PuzzleCode, String>` - The puzzle, or a message saying the code is not valid.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("{} is not a puzzle code.", code.trim());
+        let mut parts = code.trim().splitn(3, '-');
+        let seed = parts.next().ok_or_else(invalid)?;
+        let digits: Vec<char> = parts.next().ok_or_else(invalid)?.chars().collect();
+        let category = parts.next().map(str::to_string);
+
+        // Only accept what `fmt` writes, so every code has a single meaning
+        if seed.len() != SEED_DIGITS || !seed.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let seed = u64::from_str_radix(seed, 16).map_err(|_| invalid())?;
+        let [difficulty, language, options, penalty] = digits[..] else {
+            return Err(invalid());
+        };
+        let digit = |c: char, radix: u32| c.to_digit(radix).ok_or_else(invalid);
+        let difficulty = *Difficulty::ALL
+            .get(digit(difficulty, 10)? as usize)
+            .ok_or_else(invalid)?;
+        let language = *Language::ALL
+            .get(digit(language, 10)? as usize)
+            .ok_or_else(invalid)?;
+        let options = digit(options, 16)?;
+        let solve_penalty = match digit(penalty, 10)? {
+            0 => SolvePenalty::EndGame,
+            misses => SolvePenalty::Misses(misses),
+        };
+        if category.as_deref() == Some("") {
+            return Err(invalid());
+        }
+
+        Ok(PuzzleCode {
+            seed,
+            settings: GameSettings {
+                difficulty: Some(difficulty),
+                category,
+                language,
+                fold_accents: options & FOLD_ACCENTS != 0,
+                auto_reveal: AutoReveal {
+                    punctuation: options & REVEAL_PUNCTUATION != 0,
+                    digits: options & REVEAL_DIGITS != 0,
+                    symbols: options & REVEAL_SYMBOLS != 0,
+                },
+                solve_penalty,
+                ..Default::default()
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every combination of settings a code can describe, with and without a category.
+    fn every_puzzle() -> Vec<PuzzleCode> {
+        let mut puzzles = Vec::new();
+        for difficulty in Difficulty::ALL {
+            for language in Language::ALL {
+                for options in 0..16 {
+                    for solve_penalty in (1..=MAX_PENALTY)
+                        .map(SolvePenalty::Misses)
+                        .chain([SolvePenalty::EndGame])
+                    {
+                        for category in [None, Some("movies-and-tv".to_string())] {
+                            puzzles.push(PuzzleCode {
+                                seed: 0x0123_4567_89ab_cdef,
+                                settings: GameSettings {
+                                    difficulty: Some(difficulty),
+                                    category,
+                                    language,
+                                    fold_accents: options & FOLD_ACCENTS != 0,
+                                    auto_reveal: AutoReveal {
+                                        punctuation: options & REVEAL_PUNCTUATION != 0,
+                                        digits: options & REVEAL_DIGITS != 0,
+                                        symbols: options & REVEAL_SYMBOLS != 0,
+                                    },
+                                    solve_penalty,
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        puzzles
+    }
+
+    #[test]
+    fn every_puzzle_round_trips() {
+        for puzzle in every_puzzle() {
+            let code = puzzle.to_string();
+            assert_eq!(code.parse::<PuzzleCode>(), Ok(puzzle), "{}", code);
+        }
+    }
+
+    #[test]
+    fn known_code_is_stable() {
+        let code: PuzzleCode = "00000000075bcd15-2031-animals".parse().unwrap();
+        assert_eq!(code.seed, 123_456_789);
+        assert_eq!(code.settings.difficulty, Some(Difficulty::ALL[2]));
+        assert_eq!(code.settings.language, Language::ALL[0]);
+        assert!(code.settings.fold_accents && code.settings.auto_reveal.punctuation);
+        assert!(!code.settings.auto_reveal.digits && !code.settings.auto_reveal.symbols);
+        assert_eq!(code.settings.solve_penalty, SolvePenalty::Misses(1));
+        assert_eq!(code.settings.category.as_deref(), Some("animals"));
+        assert_eq!(code.to_string(), "00000000075bcd15-2031-animals");
+    }
+
+    #[test]
+    fn penalty_is_clamped_when_written() {
+        let mut puzzle = every_puzzle().remove(0);
+        puzzle.settings.solve_penalty = SolvePenalty::Misses(12);
+        let parsed: PuzzleCode = puzzle.to_string().parse().unwrap();
+        assert_eq!(parsed.settings.solve_penalty, SolvePenalty::Misses(9));
+
+        puzzle.settings.solve_penalty = SolvePenalty::Misses(0);
+        let parsed: PuzzleCode = puzzle.to_string().parse().unwrap();
+        assert_eq!(parsed.settings.solve_penalty, SolvePenalty::Misses(1));
+    }
+
+    #[test]
+    fn malformed_codes_are_rejected() {
+        for code in [
+            "",
+            "00000000075bcd15",
+            "00000000075bcd15-",
+            "00000000075bcd15-203",
+            "00000000075bcd15-20311",
+            "00000000075bcd15-203a",
+            "00000000075bcd15-203f",
+            "00000000075bcd15-9031",
+            "00000000075bcd15-2931",
+            "00000000075bcd15-20g1",
+            "00000000075bcd15-2031-",
+            "75bcd15-2031",
+            "+0000000075bcd15-2031",
+            "00000000075bcd15x-2031",
+            "0000000000075bcd15-2031",
+        ] {
+            assert!(code.parse::<PuzzleCode>().is_err(), "{}", code);
+        }
+    }
+}
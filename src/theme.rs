@@ -0,0 +1,324 @@
+//! Themes for the hangman drawing, described as shapes per stage in a small data file.
+//!
+//! Each non-blank line of a theme file that doesn't start with `#` is the theme's name, how its
+//! stages follow the game (see `StageScale`), or a shape:
+//!
+//! ```text
+//! name Classic
+//! scale parts
+//! ```
+//!
+//! A shape is made of the stages it is drawn at, its kind, its coordinates relative to the
+//! center of the drawing (`y` grows downwards), its width for lines and rings, and its colour:
+//!
+//! ```text
+//! 1..     line   -80 100 40 100  3 #a06e3c
+//! 5..=10  circle 0 -70 20          #ffffff
+//! 0..=4   ring   0 -70 20        2 #ffffff
+//! 11      rect   -30 90 30 110     #87cefa
+//! ```
+//!
+//! Stages go from 0 (no misses) to `LAST_STAGE`, then `LOST_STAGE` once the game is lost.
+//! They are written as a single number or as a range (`3..`, `3..=7`, `..=7`).
+
+use std::fs;
+use std::io::{self};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::gallows::GallowsPart;
+use crate::hangmangame::{GameStatus, HangmanGameState};
+
+/// The stage a drawing reaches on the last allowed miss.
+pub const LAST_STAGE: usize = GallowsPart::ALL.len();
+
+/// The stage of a drawing once the game is lost, after the last stage of any difficulty.
+pub const LOST_STAGE: usize = LAST_STAGE + 1;
+
+/// The themes compiled into the game, in the order they are offered to the player.
+const BUILT_IN_THEMES: [&str; 4] = [
+    include_str!("../themes/classic.theme"),
+    include_str!("../themes/snowman.theme"),
+    include_str!("../themes/balloons.theme"),
+    include_str!("../themes/flower.theme"),
+];
+
+/// A point of a drawing, relative to its center, with `y` growing downwards.
+pub type Point = (f32, f32);
+
+/// An RGB colour.
+pub type Rgb = [u8; 3];
+
+/// A shape a theme is drawn with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    /// A straight line.
+    Line {
+        from: Point,
+        to: Point,
+        width: f32,
+        color: Rgb,
+    },
+    /// A filled circle.
+    Circle {
+        center: Point,
+        radius: f32,
+        color: Rgb,
+    },
+    /// The outline of a circle.
+    Ring {
+        center: Point,
+        radius: f32,
+        width: f32,
+        color: Rgb,
+    },
+    /// A filled rectangle, from its top-left to its bottom-right corner.
+    Rect { min: Point, max: Point, color: Rgb },
+}
+
+/// How the stages of a theme follow the incorrect guesses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StageScale {
+    /// The stage is the number of gallows parts drawn (see `visible_parts`), so the first
+    /// stages are already there from the start when fewer misses are allowed.
+    #[default]
+    Parts,
+    /// The stages are spread evenly over the allowed misses, so every game starts at stage 0.
+    /// Suits themes that take something away with every miss.
+    Misses,
+}
+
+/// Something a theme can be drawn on, such as a GUI painter.
+pub trait HangmanRenderer {
+    /// Draw one shape of the theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` - The shape to draw, with coordinates relative to the center of the drawing.
+    ///
+    fn draw_shape(&mut self, shape: &Shape);
+}
+
+/// A hangman drawing: the shapes to draw at each stage of the game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// The name of the theme, as shown to the player.
+    pub name: String,
+    /// How the theme's stages follow the incorrect guesses.
+    pub scale: StageScale,
+    /// Every shape of the theme with the stages it is drawn at, in drawing order.
+    pub shapes: Vec<(RangeInclusive<usize>, Shape)>,
+}
+
+impl Theme {
+    /// The themes compiled into the game: the classic stick figure and the non-violent snowman,
+    /// balloon and flower variants.
+    pub fn built_in() -> Vec<Theme> {
+        BUILT_IN_THEMES
+            .iter()
+            .map(|contents| Theme::parse(contents).expect("Built-in themes are valid"))
+            .collect()
+    }
+
+    /// Load a theme from a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the theme file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Theme, io::Error>` - The loaded theme, or an error if the file could not be read or is not a valid theme.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, io::Error> {
+        Theme::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parse a theme from the contents of a theme file.
+    ///
+    /// # Arguments
+    ///
+    /// * `contents` - The contents of the theme file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Theme, io::Error>` - The parsed theme, or an error naming the first invalid line.
+    pub fn parse(contents: &str) -> Result<Self, io::Error> {
+        let mut name = None;
+        let mut scale = StageScale::default();
+        let mut shapes = Vec::new();
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(theme_name) = line.strip_prefix("name ") {
+                name = Some(theme_name.trim().to_string());
+            } else if let Some(theme_scale) = line.strip_prefix("scale ") {
+                scale = match theme_scale.trim() {
+                    "parts" => StageScale::Parts,
+                    "misses" => StageScale::Misses,
+                    other => {
+                        return Err(invalid_theme_error(format!(
+                            "line {}: unknown scale {}, expected parts or misses",
+                            number + 1,
+                            other
+                        )))
+                    }
+                };
+            } else {
+                let shape = parse_shape(line).map_err(|message| {
+                    invalid_theme_error(format!("line {}: {}", number + 1, message))
+                })?;
+                shapes.push(shape);
+            }
+        }
+
+        Ok(Theme {
+            name: name.ok_or_else(|| invalid_theme_error("no name line".to_string()))?,
+            scale,
+            shapes,
+        })
+    }
+
+    /// The stage of the drawing for a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - The game whose incorrect guesses decide the stage.
+    ///
+    pub fn stage(&self, game_state: &HangmanGameState) -> usize {
+        if game_state.status() == GameStatus::Lost {
+            return LOST_STAGE;
+        }
+
+        match self.scale {
+            StageScale::Parts => game_state.gallows_parts().len(),
+            StageScale::Misses => {
+                let allowed = game_state.allowed_misses() as usize;
+                let misses = (game_state.misses() as usize).min(allowed);
+                if allowed == 0 {
+                    0
+                } else {
+                    (misses * LAST_STAGE).div_ceil(allowed)
+                }
+            }
+        }
+    }
+
+    /// Draw the shapes of a stage.
+    ///
+    /// # Arguments
+    ///
+    /// * `stage` - The stage to draw, as returned by `stage`.
+    /// * `renderer` - The renderer to draw on.
+    ///
+    pub fn render(&self, stage: usize, renderer: &mut dyn HangmanRenderer) {
+        for (stages, shape) in &self.shapes {
+            if stages.contains(&stage) {
+                renderer.draw_shape(shape);
+            }
+        }
+    }
+}
+
+/// Parse a shape line such as `1.. line -80 100 40 100 3 #a06e3c`.
+fn parse_shape(line: &str) -> Result<(RangeInclusive<usize>, Shape), String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let (stages, kind, values) = match fields.as_slice() {
+        [stages, kind, values @ ..] => (parse_stages(stages)?, *kind, values),
+        _ => return Err("expected stages, a shape and its coordinates".to_string()),
+    };
+
+    // Every shape ends with its colour, after its numbers
+    let (color, numbers) = match values.split_last() {
+        Some((color, numbers)) => (parse_color(color)?, numbers),
+        None => return Err(format!("{} has no colour", kind)),
+    };
+    let numbers = numbers
+        .iter()
+        .map(|number| {
+            number
+                .parse::<f32>()
+                .map_err(|_| format!("{} is not a number", number))
+        })
+        .collect::<Result<Vec<f32>, String>>()?;
+
+    let shape = match (kind, numbers.as_slice()) {
+        ("line", &[x1, y1, x2, y2, width]) => Shape::Line {
+            from: (x1, y1),
+            to: (x2, y2),
+            width,
+            color,
+        },
+        ("circle", &[x, y, radius]) => Shape::Circle {
+            center: (x, y),
+            radius,
+            color,
+        },
+        ("ring", &[x, y, radius, width]) => Shape::Ring {
+            center: (x, y),
+            radius,
+            width,
+            color,
+        },
+        ("rect", &[x1, y1, x2, y2]) => Shape::Rect {
+            min: (x1, y1),
+            max: (x2, y2),
+            color,
+        },
+        ("line", _) => return Err("line takes x1 y1 x2 y2 width colour".to_string()),
+        ("circle", _) => return Err("circle takes x y radius colour".to_string()),
+        ("ring", _) => return Err("ring takes x y radius width colour".to_string()),
+        ("rect", _) => return Err("rect takes x1 y1 x2 y2 colour".to_string()),
+        _ => return Err(format!("unknown shape {}", kind)),
+    };
+
+    Ok((stages, shape))
+}
+
+/// Parse the stages a shape is drawn at: `3`, `3..`, `3..=7` or `..=7`.
+fn parse_stages(stages: &str) -> Result<RangeInclusive<usize>, String> {
+    let parse = |stage: &str| {
+        stage
+            .parse::<usize>()
+            .map_err(|_| format!("{} is not a stage", stages))
+    };
+
+    match stages.split_once("..") {
+        None => parse(stages).map(|stage| stage..=stage),
+        Some((start, end)) => {
+            let start = if start.is_empty() { 0 } else { parse(start)? };
+            let end = match end.strip_prefix('=') {
+                Some(end) => parse(end)?,
+                None if end.is_empty() => usize::MAX,
+                None => return Err(format!("{} must use ..= for its end", stages)),
+            };
+            Ok(start..=end)
+        }
+    }
+}
+
+/// Parse a colour written as `#rrggbb`.
+fn parse_color(color: &str) -> Result<Rgb, String> {
+    let invalid = || format!("{} is not a #rrggbb colour", color);
+    let hex = color
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6)
+        .ok_or_else(invalid)?;
+
+    let channel = |start: usize| {
+        hex.get(start..start + 2)
+            .and_then(|channel| u8::from_str_radix(channel, 16).ok())
+            .ok_or_else(invalid)
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn invalid_theme_error(message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid theme: {}", message),
+    )
+}
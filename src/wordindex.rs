@@ -0,0 +1,309 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use rand::Rng;
+
+use crate::difficulty::{letter_frequency_score, unique_letter_count, word_length, WordFilter};
+
+/// How many random words `random_word_where` draws before it looks through every word that
+/// passes the filter for ones that pass the check.
+const RANDOM_DRAWS: usize = 8;
+
+/// A word list parsed once into buckets, so random words can be picked without rescanning it.
+///
+/// Words are bucketed by length and then by unique-letter count, and each bucket is sorted by
+/// `letter_frequency_score`. Counting the words that pass a filter only visits the buckets
+/// whose length and unique-letter count pass it, and finds the frequency band in each with a
+/// binary search. The first word picked with a filter collects the positions of the words that
+/// pass it; every later pick with the same filter is a single lookup, whatever the list's size.
+#[derive(Debug, Default)]
+pub struct WordIndex {
+    /// Every word in the list, in the order it was given.
+    words: Vec<String>,
+    /// Length -> unique-letter count -> (frequency score, position in `words`), sorted by score.
+    buckets: BTreeMap<usize, BTreeMap<usize, Vec<(u32, usize)>>>,
+    /// The positions in `words` of the words passing each filter picked with so far, in the
+    /// order the buckets are visited.
+    picks: Mutex<HashMap<WordFilter, Arc<[usize]>>>,
+}
+
+impl Clone for WordIndex {
+    /// Copy the index. The positions collected for picking are collected again as needed.
+    fn clone(&self) -> Self {
+        WordIndex {
+            words: self.words.clone(),
+            buckets: self.buckets.clone(),
+            picks: Mutex::default(),
+        }
+    }
+}
+
+impl WordIndex {
+    /// Build an index over a list of words.
+    ///
+    /// # Arguments
+    ///
+    /// * `words` - The words to index.
+    ///
+    pub fn new(words: Vec<String>) -> Self {
+        let mut buckets: BTreeMap<usize, BTreeMap<usize, Vec<(u32, usize)>>> = BTreeMap::new();
+
+        for (position, word) in words.iter().enumerate() {
+            buckets
+                .entry(word_length(word))
+                .or_default()
+                .entry(unique_letter_count(word))
+                .or_default()
+                .push((letter_frequency_score(word), position));
+        }
+
+        for bucket in buckets.values_mut().flat_map(BTreeMap::values_mut) {
+            bucket.sort_unstable();
+        }
+
+        WordIndex {
+            words,
+            buckets,
+            picks: Mutex::default(),
+        }
+    }
+
+    /// Every word in the index, in the order it was given.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// The number of words in the index.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Check whether the index has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Count the words that pass a filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter to count words for.
+    ///
+    pub fn count_matching(&self, filter: &WordFilter) -> usize {
+        // Ranging over the buckets with an empty range would panic
+        if filter.is_empty() {
+            return 0;
+        }
+
+        self.matching_slices(filter).map(<[_]>::len).sum()
+    }
+
+    /// Pick a random word that passes a filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter the word must pass.
+    /// * `rng` - The random number generator to pick with.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The picked word, or `None` if no word passes the filter.
+    pub fn random_word<R: Rng + ?Sized>(&self, filter: &WordFilter, rng: &mut R) -> Option<&str> {
+        self.random_word_where(filter, rng, |_| true)
+    }
+
+    /// Pick a random word that passes a filter and a check the index can't answer, such as
+    /// whether the word can be played in a game. When most words pass the check, this is as
+    /// quick as `random_word`, and picks the same word with the same generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter the word must pass.
+    /// * `rng` - The random number generator to pick with.
+    /// * `check` - Whether a word that passes the filter may be picked.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The picked word, or `None` if no word passes both.
+    pub fn random_word_where<R: Rng + ?Sized>(
+        &self,
+        filter: &WordFilter,
+        rng: &mut R,
+        check: impl Fn(&str) -> bool,
+    ) -> Option<&str> {
+        // Ranging over the buckets with an empty range would panic
+        if filter.is_empty() {
+            return None;
+        }
+
+        let matching = self.matching_positions(filter);
+        for _ in 0..RANDOM_DRAWS {
+            let word = &self.words[*choose(&matching, rng)?];
+            if check(word) {
+                return Some(word);
+            }
+        }
+
+        // The draws kept missing, so only pick from the words that pass the check
+        let passing: Vec<usize> = matching
+            .iter()
+            .copied()
+            .filter(|position| check(&self.words[*position]))
+            .collect();
+        Some(&self.words[*choose(&passing, rng)?])
+    }
+
+    /// The positions in `words` of the words that pass a filter, collected the first time the
+    /// filter is used.
+    fn matching_positions(&self, filter: &WordFilter) -> Arc<[usize]> {
+        let mut picks = self.picks.lock().unwrap_or_else(PoisonError::into_inner);
+        picks
+            .entry(filter.clone())
+            .or_insert_with(|| {
+                self.matching_slices(filter)
+                    .flatten()
+                    .map(|(_, position)| *position)
+                    .collect()
+            })
+            .clone()
+    }
+
+    /// The parts of each bucket whose words pass a filter.
+    fn matching_slices<'a>(
+        &'a self,
+        filter: &'a WordFilter,
+    ) -> impl Iterator<Item = &'a [(u32, usize)]> + 'a {
+        self.buckets
+            .range(filter.length.clone())
+            .flat_map(move |(_, by_unique_letters)| {
+                by_unique_letters.range(filter.unique_letters.clone())
+            })
+            .map(move |(_, bucket)| {
+                let start =
+                    bucket.partition_point(|(score, _)| score < filter.frequency_band.start());
+                let end = bucket.partition_point(|(score, _)| score <= filter.frequency_band.end());
+                &bucket[start..end.max(start)]
+            })
+    }
+}
+
+/// Pick a random position, or `None` if there are none.
+fn choose<'a, R: Rng + ?Sized>(positions: &'a [usize], rng: &mut R) -> Option<&'a usize> {
+    if positions.is_empty() {
+        return None;
+    }
+
+    // Drawn as a u64 so a seed picks the same word on 32-bit and 64-bit targets
+    positions.get(rng.gen_range(0..positions.len() as u64) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::RangeInclusive;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::wordsource::{EmbeddedWordList, WordSource};
+
+    /// The filters to check the index with: every difficulty's, and some edge cases.
+    fn filters() -> Vec<WordFilter> {
+        let mut filters: Vec<WordFilter> = (Difficulty::ALL.iter())
+            .map(|difficulty| difficulty.profile().words)
+            .collect();
+        filters.push(WordFilter::any());
+        filters.push(WordFilter {
+            unique_letters: 3..=3,
+            frequency_band: 40..=60,
+            ..WordFilter::any()
+        });
+        // No word passes an empty range
+        filters.push(WordFilter {
+            length: RangeInclusive::new(9, 4),
+            ..WordFilter::any()
+        });
+        filters
+    }
+
+    /// The positions of the words that pass a filter, found by checking every word.
+    fn rescan(words: &[String], filter: &WordFilter) -> Vec<usize> {
+        (0..words.len())
+            .filter(|position| !filter.is_empty() && filter.matches(&words[*position]))
+            .collect()
+    }
+
+    #[test]
+    fn filtering_finds_the_same_words_as_rescanning() {
+        let word_source = EmbeddedWordList;
+        let index = word_source.index();
+        for filter in filters() {
+            let expected = rescan(index.words(), &filter);
+            assert_eq!(
+                index.count_matching(&filter),
+                expected.len(),
+                "{:?}",
+                filter
+            );
+            if filter.is_empty() {
+                continue;
+            }
+
+            let mut positions = index.matching_positions(&filter).to_vec();
+            positions.sort_unstable();
+            assert_eq!(positions, expected, "{:?}", filter);
+        }
+    }
+
+    #[test]
+    fn random_words_pass_the_filter() {
+        let word_source = EmbeddedWordList;
+        let index = word_source.index();
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for filter in filters() {
+            let expected = rescan(index.words(), &filter);
+            for _ in 0..50 {
+                match index.random_word(&filter, &mut rng) {
+                    Some(word) => assert!(filter.matches(word), "{} {:?}", word, filter),
+                    None => assert!(expected.is_empty()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_words_pass_the_check_even_when_few_do() {
+        let words: Vec<String> = (0..100).map(|n| format!("word{}", n)).collect();
+        let index = WordIndex::new(words);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..20 {
+            let word =
+                index.random_word_where(&WordFilter::any(), &mut rng, |word| word == "word42");
+            assert_eq!(word, Some("word42"));
+        }
+        let none = index.random_word_where(&WordFilter::any(), &mut rng, |_| false);
+        assert_eq!(none, None);
+    }
+
+    #[test]
+    fn same_seed_picks_the_same_word() {
+        let word_source = EmbeddedWordList;
+        let index = word_source.index();
+        let filter = Difficulty::Hard.profile().words;
+        let pick = |seed| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            index.random_word(&filter, &mut rng).map(str::to_string)
+        };
+        assert_eq!(pick(3), pick(3));
+        // A copy of the index collects the positions again, in the same order
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        assert_eq!(
+            index
+                .clone()
+                .random_word(&filter, &mut rng)
+                .map(str::to_string),
+            pick(3)
+        );
+    }
+}
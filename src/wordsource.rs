@@ -0,0 +1,419 @@
+use std::fs;
+use std::io::{self};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+use crate::alphabet::Language;
+use crate::wordindex::WordIndex;
+
+/// The category packs compiled into the game, as (category, file contents) pairs.
+const EMBEDDED_CATEGORIES: [(&str, &str); 6] = [
+    (
+        "animals",
+        include_str!("../worldlist/categories/animals.txt"),
+    ),
+    (
+        "countries",
+        include_str!("../worldlist/categories/countries.txt"),
+    ),
+    ("food", include_str!("../worldlist/categories/food.txt")),
+    ("movies", include_str!("../worldlist/categories/movies.txt")),
+    (
+        "programming",
+        include_str!("../worldlist/categories/programming.txt"),
+    ),
+    ("sports", include_str!("../worldlist/categories/sports.txt")),
+];
+
+/// The word packs compiled into the game for languages other than English.
+const EMBEDDED_LANGUAGE_PACKS: [(Language, &str); 6] = [
+    (
+        Language::French,
+        include_str!("../worldlist/languages/french.txt"),
+    ),
+    (
+        Language::German,
+        include_str!("../worldlist/languages/german.txt"),
+    ),
+    (
+        Language::Spanish,
+        include_str!("../worldlist/languages/spanish.txt"),
+    ),
+    (
+        Language::Polish,
+        include_str!("../worldlist/languages/polish.txt"),
+    ),
+    (
+        Language::Russian,
+        include_str!("../worldlist/languages/russian.txt"),
+    ),
+    (
+        Language::Greek,
+        include_str!("../worldlist/languages/greek.txt"),
+    ),
+];
+
+/// A source of words for the Hangman game to pick from.
+pub trait WordSource: Send + Sync {
+    /// A short description of where the words come from, as shown to the player.
+    fn name(&self) -> String;
+
+    /// The index of all the words this source provides, built once when the source is loaded.
+    fn index(&self) -> &WordIndex;
+
+    /// The names of the categories this source's words are tagged with, in alphabetical order.
+    ///
+    /// Sources without categories return an empty list.
+    fn categories(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The index of the words tagged with a category.
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - The name of the category, as returned by `categories`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<&WordIndex, io::Error>` - The index, or an error if the source has no such category.
+    fn category_index(&self, category: &str) -> Result<&WordIndex, io::Error> {
+        Err(unknown_category_error(category))
+    }
+}
+
+/// The word list compiled into the game.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EmbeddedWordList;
+
+/// The word pack compiled into the game for a language other than English.
+#[derive(Clone, Copy, Debug)]
+pub struct LanguagePack {
+    language: Language,
+}
+
+/// An in-memory list of words.
+#[derive(Clone, Debug, Default)]
+pub struct WordList {
+    name: String,
+    index: WordIndex,
+}
+
+/// A word list loaded from a user-supplied file, one word or phrase per line.
+#[derive(Clone, Debug)]
+pub struct WordFile {
+    path: PathBuf,
+    index: WordIndex,
+}
+
+/// A directory of word list files, one file per category (e.g. `animals.txt`).
+#[derive(Clone, Debug)]
+pub struct CategoryDirectory {
+    path: PathBuf,
+    index: WordIndex,
+    categories: Vec<(String, WordIndex)>,
+}
+
+impl WordSource for EmbeddedWordList {
+    fn name(&self) -> String {
+        "Embedded word list".to_string()
+    }
+
+    fn index(&self) -> &WordIndex {
+        static INDEX: OnceLock<WordIndex> = OnceLock::new();
+        INDEX.get_or_init(|| WordIndex::new(parse_words(include_str!("../worldlist/wordlist.txt"))))
+    }
+
+    fn categories(&self) -> Vec<String> {
+        EMBEDDED_CATEGORIES
+            .iter()
+            .map(|(category, _)| category.to_string())
+            .collect()
+    }
+
+    fn category_index(&self, category: &str) -> Result<&WordIndex, io::Error> {
+        static INDEXES: OnceLock<Vec<WordIndex>> = OnceLock::new();
+        let indexes = INDEXES.get_or_init(|| {
+            EMBEDDED_CATEGORIES
+                .iter()
+                .map(|(_, contents)| WordIndex::new(parse_words(contents)))
+                .collect()
+        });
+
+        EMBEDDED_CATEGORIES
+            .iter()
+            .position(|(name, _)| *name == category)
+            .map(|position| &indexes[position])
+            .ok_or_else(|| unknown_category_error(category))
+    }
+}
+
+impl LanguagePack {
+    /// The embedded word pack for a language, or `None` if the language has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language to get the word pack for.
+    ///
+    pub fn new(language: Language) -> Option<Self> {
+        EMBEDDED_LANGUAGE_PACKS
+            .iter()
+            .find(|(pack_language, _)| *pack_language == language)
+            .map(|&(language, _)| LanguagePack { language })
+    }
+}
+
+impl WordSource for LanguagePack {
+    fn name(&self) -> String {
+        format!("{} word pack", self.language.name())
+    }
+
+    fn index(&self) -> &WordIndex {
+        static INDEXES: OnceLock<Vec<WordIndex>> = OnceLock::new();
+        let indexes = INDEXES.get_or_init(|| {
+            EMBEDDED_LANGUAGE_PACKS
+                .iter()
+                .map(|(_, contents)| WordIndex::new(parse_words(contents)))
+                .collect()
+        });
+
+        let position = EMBEDDED_LANGUAGE_PACKS
+            .iter()
+            .position(|(language, _)| *language == self.language)
+            .expect("Language packs are only created from EMBEDDED_LANGUAGE_PACKS");
+        &indexes[position]
+    }
+}
+
+impl WordList {
+    /// Create a word list from words held in memory.
+    ///
+    /// Blank words are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the list, as shown to the player.
+    /// * `words` - The words in the list.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WordList, io::Error>` - The list, or an error if it has no words.
+    pub fn new(name: impl Into<String>, words: Vec<String>) -> Result<Self, io::Error> {
+        let name = name.into();
+        let words: Vec<String> = words
+            .iter()
+            .map(|word| word.trim())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        if words.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("No words found in {}", name),
+            ));
+        }
+
+        Ok(WordList {
+            name,
+            index: WordIndex::new(words),
+        })
+    }
+}
+
+impl WordSource for WordList {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn index(&self) -> &WordIndex {
+        &self.index
+    }
+}
+
+impl WordFile {
+    /// Load a word list from a file.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to load.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<WordFile, io::Error>` - The loaded list, or an error if the file could not be read or has no words.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, io::Error> {
+        let path = path.as_ref();
+        let words = parse_words(&fs::read_to_string(path)?);
+
+        if words.is_empty() {
+            return Err(no_words_error(path));
+        }
+
+        Ok(WordFile {
+            path: path.to_path_buf(),
+            index: WordIndex::new(words),
+        })
+    }
+}
+
+impl WordSource for WordFile {
+    fn name(&self) -> String {
+        format!("File: {}", self.path.display())
+    }
+
+    fn index(&self) -> &WordIndex {
+        &self.index
+    }
+}
+
+impl CategoryDirectory {
+    /// Load every `.txt` file in a directory as a category named after the file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the directory to load.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<CategoryDirectory, io::Error>` - The loaded categories, or an error if the directory could not be read or has no words.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, io::Error> {
+        let path = path.as_ref();
+        let mut categories = Vec::new();
+
+        for entry in fs::read_dir(path)? {
+            let file_path = entry?.path();
+            if file_path
+                .extension()
+                .is_some_and(|extension| extension == "txt")
+            {
+                let category = file_path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let words = parse_words(&fs::read_to_string(&file_path)?);
+                if !words.is_empty() {
+                    categories.push((category, words));
+                }
+            }
+        }
+
+        if categories.is_empty() {
+            return Err(no_words_error(path));
+        }
+
+        // Keep the categories in a stable order, whatever order the directory was read in
+        categories.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let all_words = categories
+            .iter()
+            .flat_map(|(_, words)| words.iter().cloned())
+            .collect();
+
+        Ok(CategoryDirectory {
+            path: path.to_path_buf(),
+            index: WordIndex::new(all_words),
+            categories: categories
+                .into_iter()
+                .map(|(category, words)| (category, WordIndex::new(words)))
+                .collect(),
+        })
+    }
+}
+
+impl WordSource for CategoryDirectory {
+    fn name(&self) -> String {
+        format!("Directory: {}", self.path.display())
+    }
+
+    fn index(&self) -> &WordIndex {
+        &self.index
+    }
+
+    fn categories(&self) -> Vec<String> {
+        self.categories
+            .iter()
+            .map(|(category, _)| category.clone())
+            .collect()
+    }
+
+    fn category_index(&self, category: &str) -> Result<&WordIndex, io::Error> {
+        self.categories
+            .iter()
+            .find(|(name, _)| name == category)
+            .map(|(_, index)| index)
+            .ok_or_else(|| unknown_category_error(category))
+    }
+}
+
+/// Open a word file or a directory of category files, depending on what the path points at.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file or directory to load.
+///
+/// # Returns
+///
+/// * `Result<Arc<dyn WordSource>, io::Error>` - The loaded word source, or an error if it could not be read.
+pub fn open_word_source(path: impl AsRef<Path>) -> Result<Arc<dyn WordSource>, io::Error> {
+    let path = path.as_ref();
+
+    if path.is_dir() {
+        Ok(Arc::new(CategoryDirectory::open(path)?))
+    } else {
+        Ok(Arc::new(WordFile::open(path)?))
+    }
+}
+
+/// The word source compiled into the game for a language: the embedded word list and its
+/// categories for English, and the language's word pack otherwise.
+///
+/// # Arguments
+///
+/// * `language` - The language to get the word source for.
+///
+pub fn embedded_word_source(language: Language) -> Arc<dyn WordSource> {
+    match LanguagePack::new(language) {
+        Some(pack) => Arc::new(pack),
+        None => Arc::new(EmbeddedWordList),
+    }
+}
+
+/// Split the contents of a word list into words, skipping blank lines and `#` comments.
+fn parse_words(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Turn a category name such as `programming` into a clue shown to the player, such as `Programming`.
+///
+/// # Arguments
+///
+/// * `category` - The name of the category.
+///
+pub fn category_display_name(category: &str) -> String {
+    let mut chars = category.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn unknown_category_error(category: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("No category named {}", category),
+    )
+}
+
+fn no_words_error(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("No words found in {}", path.display()),
+    )
+}
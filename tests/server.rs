@@ -0,0 +1,156 @@
+//! Plays a room on the multiplayer server over real connections: two players and a spectator.
+
+#![cfg(feature = "server")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+use rusty_hangman::{ClientMessage, GameView, ServerMessage};
+
+/// The server binary, killed when the test ends.
+struct Server {
+    process: Child,
+    address: String,
+}
+
+impl Server {
+    /// Start the server on a free port, and wait until it listens.
+    fn start() -> Self {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_rusty-hangman-server"))
+            .args(["--server", "127.0.0.1:0"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut line = String::new();
+        BufReader::new(process.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let address = line.trim().rsplit(' ').next().unwrap().to_string();
+        Server { process, address }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// A connection to the server.
+struct Client {
+    writer: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Client {
+    fn connect(server: &Server) -> Self {
+        let writer = TcpStream::connect(&server.address).unwrap();
+        writer
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let reader = BufReader::new(writer.try_clone().unwrap());
+        Client { writer, reader }
+    }
+
+    fn send(&mut self, message: ClientMessage) {
+        let mut line = serde_json::to_string(&message).unwrap();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).unwrap();
+    }
+
+    fn receive(&mut self) -> ServerMessage {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    /// Receive the next message, which must be a state update, and return its line.
+    fn receive_state(&mut self) -> (String, GameView) {
+        match self.receive() {
+            ServerMessage::StateUpdate { message, state } => (message, state),
+            other => panic!("expected a state update, got {:?}", other),
+        }
+    }
+
+    fn receive_error(&mut self) -> String {
+        match self.receive() {
+            ServerMessage::Error { message } => message,
+            other => panic!("expected an error, got {:?}", other),
+        }
+    }
+
+    fn join(server: &Server, name: &str) -> Self {
+        let mut client = Client::connect(server);
+        client.send(ClientMessage::Join {
+            room: "test".to_string(),
+            name: name.to_string(),
+            difficulty: None,
+        });
+        assert!(matches!(client.receive(), ServerMessage::Joined { .. }));
+        client
+    }
+}
+
+#[test]
+fn players_take_turns_and_spectators_never_see_the_phrase() {
+    let server = Server::start();
+    let mut ann = Client::join(&server, "Ann");
+    ann.receive_state();
+    let mut bob = Client::join(&server, "Bob");
+    let (message, state) = ann.receive_state();
+    assert_eq!(message, "Bob joined. The game starts with the first guess.");
+    bob.receive_state();
+    // Bob joined before the first guess, so the game has both players
+    let names: Vec<&str> = (state.players.iter()).map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["Ann", "Bob"]);
+
+    let mut spectator = Client::connect(&server);
+    spectator.send(ClientMessage::Watch {
+        room: "test".to_string(),
+    });
+    assert!(matches!(
+        spectator.receive(),
+        ServerMessage::Watching { .. }
+    ));
+    spectator.receive_state();
+
+    bob.send(ClientMessage::Guess { letter: 'E' });
+    assert_eq!(bob.receive_error(), "It's Ann's turn.");
+    ann.send(ClientMessage::Guess { letter: 'E' });
+    for client in [&mut ann, &mut bob, &mut spectator] {
+        assert_eq!(client.receive_state().1.turn, 1);
+    }
+    ann.send(ClientMessage::Guess { letter: 'A' });
+    assert_eq!(ann.receive_error(), "It's Bob's turn.");
+
+    // Wrong solve attempts in turn until the game is lost
+    let mut players = [ann, bob];
+    let mut turn = 1;
+    loop {
+        players[turn].send(ClientMessage::Solve {
+            attempt: "Q".to_string(),
+        });
+        let (_, state) = players[0].receive_state();
+        players[1].receive_state();
+        let (_, spectator_state) = spectator.receive_state();
+        assert_eq!(spectator_state.phrase, None);
+        if state.game_over {
+            assert!(state.phrase.is_some());
+            assert!(spectator_state.game_over);
+            break;
+        }
+        turn = state.turn;
+    }
+    for player in &mut players {
+        assert!(matches!(player.receive(), ServerMessage::GameOver { .. }));
+    }
+
+    // The spectator's next message is the chat, so the end of the game wasn't announced to it
+    players[0].send(ClientMessage::Chat {
+        text: "Well played".to_string(),
+    });
+    assert!(matches!(spectator.receive(), ServerMessage::Chat { .. }));
+}